@@ -0,0 +1,38 @@
+//! Attribute classifications shared by `minihtml` and `minihtml-codegen`,
+//! so that the checks done by the `html!` macro and at runtime cannot drift apart.
+
+/// The attributes that take a URL, compared case-insensitively.
+///
+/// The names do not depend on the element, e.g. `data` is a URL on `object` only,
+/// but is treated as a URL everywhere.
+///
+/// Attributes that contain URLs in other syntaxes are not in this list and are not checked:
+/// `srcset` and `imagesrcset` (comma-separated candidates), `ping` (space-separated list),
+/// `style` (CSS `url()`) and `content` (e.g. `<meta http-equiv="refresh">`).
+pub const URL_ATTRS: &[&str] = &[
+    "action",
+    "background",
+    "cite",
+    "codebase",
+    "data",
+    "formaction",
+    "href",
+    "icon",
+    "longdesc",
+    "manifest",
+    "poster",
+    "src",
+    "xlink:href",
+];
+
+/// Checks whether the attribute takes a URL, i.e. is in `URL_ATTRS`.
+pub fn is_url_attr(name: &str) -> bool {
+    URL_ATTRS.iter().any(|url| name.eq_ignore_ascii_case(url))
+}
+
+/// Checks whether the attribute name is an event handler, i.e. starts with `on`.
+///
+/// All standard attributes starting with `on` are event handlers.
+pub fn is_event_handler(name: &str) -> bool {
+    name.len() > 2 && name.as_bytes()[..2].eq_ignore_ascii_case(b"on")
+}
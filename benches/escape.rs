@@ -0,0 +1,66 @@
+//! Compares `escape_text` with the per-character escaper it replaced.
+
+use std::fmt::{self, Write};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use minihtml::escape_text;
+
+/// The per-character escaper that `escape_text` replaced.
+struct Reference<'t>(&'t str);
+
+impl<'t> fmt::Display for Reference<'t> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for char in self.0.chars() {
+            let escape = match char {
+                '&' => "&amp;",
+                '<' => "&lt;",
+                '>' => "&gt;",
+                '\'' => "&apos;",
+                '"' => "&quot;",
+                _ => {
+                    write!(f, "{}", char)?;
+                    continue;
+                }
+            };
+            write!(f, "{}", escape)?;
+        }
+        Ok(())
+    }
+}
+
+fn bench_escape(c: &mut Criterion) {
+    let inputs = [
+        (
+            "plain",
+            "Lorem ipsum dolor sit amet, consectetur adipiscing elit. ".repeat(64),
+        ),
+        (
+            "mixed",
+            "if a < b && c > \"d\" then 'e' else f; ".repeat(64),
+        ),
+        ("special", "<>&'\"".repeat(256)),
+    ];
+
+    for (name, input) in &inputs {
+        let mut group = c.benchmark_group(format!("escape/{}", name));
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_function("escape_text", |b| {
+            let mut output = String::with_capacity(input.len() * 6);
+            b.iter(|| {
+                output.clear();
+                escape_text(black_box(input), &mut output).unwrap();
+            })
+        });
+        group.bench_function("per_char", |b| {
+            let mut output = String::with_capacity(input.len() * 6);
+            b.iter(|| {
+                output.clear();
+                write!(output, "{}", Reference(black_box(input))).unwrap();
+            })
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_escape);
+criterion_main!(benches);
@@ -0,0 +1,154 @@
+//! Compares a page generated by `html!` with the same page written piece by piece,
+//! which is what `html!` generated before static fragments were folded together.
+
+use std::fmt::{self, Write};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use minihtml::{html, ToHtmlNode};
+
+struct Post {
+    title: &'static str,
+    author: &'static str,
+    body: &'static str,
+}
+
+fn posts() -> Vec<Post> {
+    (0..20)
+        .map(|_| Post {
+            title: "Hello <world>",
+            author: "someone",
+            body: "Lorem ipsum dolor sit amet, consectetur adipiscing elit & more.",
+        })
+        .collect()
+}
+
+fn folded(posts: &[Post]) -> String {
+    let page = html! {
+        html(lang = "en") {
+            head {
+                meta(charset = "utf-8");
+                title { +"Blog" }
+                link(rel = "stylesheet", href = "/style.css");
+            }
+            body.page {
+                nav #menu {
+                    a.active(href = "/") { +"Home" }
+                    a(href = "/about") { +"About" }
+                }
+                main {
+                    @for post in posts {
+                        article.post {
+                            h2.title { +post.title }
+                            p.meta { +"by "; +post.author }
+                            p { +post.body }
+                        }
+                    }
+                }
+                footer { +"Copyright & stuff" }
+            }
+        }
+    };
+    let mut output = String::new();
+    page.fmt(&mut output).unwrap();
+    output
+}
+
+fn unfolded(posts: &[Post]) -> String {
+    let page = |output: &mut dyn Write| -> fmt::Result {
+        write!(output, "<html")?;
+        write!(output, " lang=\"")?;
+        ToHtmlNode::fmt("en", output)?;
+        write!(output, "\"")?;
+        write!(output, ">")?;
+        write!(output, "<head")?;
+        write!(output, ">")?;
+        write!(output, "<meta")?;
+        write!(output, " charset=\"")?;
+        ToHtmlNode::fmt("utf-8", output)?;
+        write!(output, "\"")?;
+        write!(output, "/>")?;
+        write!(output, "<title")?;
+        write!(output, ">")?;
+        ToHtmlNode::fmt("Blog", output)?;
+        write!(output, "</title>")?;
+        write!(output, "<link")?;
+        write!(output, " rel=\"")?;
+        ToHtmlNode::fmt("stylesheet", output)?;
+        write!(output, "\"")?;
+        write!(output, " href=\"")?;
+        ToHtmlNode::fmt("/style.css", output)?;
+        write!(output, "\"")?;
+        write!(output, "/>")?;
+        write!(output, "</head>")?;
+        write!(output, "<body")?;
+        write!(output, " class=\"page\"")?;
+        write!(output, ">")?;
+        write!(output, "<nav")?;
+        write!(output, " id=\"menu\"")?;
+        write!(output, ">")?;
+        write!(output, "<a")?;
+        write!(output, " class=\"active\"")?;
+        write!(output, " href=\"")?;
+        ToHtmlNode::fmt("/", output)?;
+        write!(output, "\"")?;
+        write!(output, ">")?;
+        ToHtmlNode::fmt("Home", output)?;
+        write!(output, "</a>")?;
+        write!(output, "<a")?;
+        write!(output, " href=\"")?;
+        ToHtmlNode::fmt("/about", output)?;
+        write!(output, "\"")?;
+        write!(output, ">")?;
+        ToHtmlNode::fmt("About", output)?;
+        write!(output, "</a>")?;
+        write!(output, "</nav>")?;
+        write!(output, "<main")?;
+        write!(output, ">")?;
+        for post in posts {
+            write!(output, "<article")?;
+            write!(output, " class=\"post\"")?;
+            write!(output, ">")?;
+            write!(output, "<h2")?;
+            write!(output, " class=\"title\"")?;
+            write!(output, ">")?;
+            ToHtmlNode::fmt(post.title, output)?;
+            write!(output, "</h2>")?;
+            write!(output, "<p")?;
+            write!(output, " class=\"meta\"")?;
+            write!(output, ">")?;
+            ToHtmlNode::fmt("by ", output)?;
+            ToHtmlNode::fmt(post.author, output)?;
+            write!(output, "</p>")?;
+            write!(output, "<p")?;
+            write!(output, ">")?;
+            ToHtmlNode::fmt(post.body, output)?;
+            write!(output, "</p>")?;
+            write!(output, "</article>")?;
+        }
+        write!(output, "</main>")?;
+        write!(output, "<footer")?;
+        write!(output, ">")?;
+        ToHtmlNode::fmt("Copyright & stuff", output)?;
+        write!(output, "</footer>")?;
+        write!(output, "</body>")?;
+        write!(output, "</html>")?;
+        Ok(())
+    };
+    let mut output = String::new();
+    page(&mut output).unwrap();
+    output
+}
+
+fn bench_page(c: &mut Criterion) {
+    let posts = posts();
+    assert_eq!(folded(&posts), unfolded(&posts));
+
+    let mut group = c.benchmark_group("page");
+    group.throughput(Throughput::Bytes(folded(&posts).len() as u64));
+    group.bench_function("folded", |b| b.iter(|| folded(black_box(&posts))));
+    group.bench_function("unfolded", |b| b.iter(|| unfolded(black_box(&posts))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_page);
+criterion_main!(benches);
@@ -0,0 +1,153 @@
+use proc_macro2::TokenStream;
+use proc_quote::quote;
+
+use crate::parse;
+
+/// An estimate of the number of bytes written by some nodes.
+#[derive(Default)]
+pub struct Hint {
+    /// The number of bytes in static literals.
+    fixed: usize,
+    /// Expressions evaluating to the size hints of dynamic nodes.
+    dynamic: Vec<TokenStream>,
+}
+
+impl Hint {
+    /// Estimates the size of the nodes.
+    ///
+    /// Nodes in control flow and components are not counted since their output is unknown.
+    /// Dynamic nodes are only counted if the expression is free of side effects and cannot refer
+    /// to a binding introduced in the template.
+    pub fn of_nodes(nodes: &[parse::HtmlNode]) -> Self {
+        let mut hint = Self::default();
+        hint.add_nodes(nodes, true);
+        hint
+    }
+
+    /// Generates an expression that computes the size hint.
+    pub fn to_closure(&self) -> TokenStream {
+        let fixed = self.fixed;
+        let dynamic = &self.dynamic;
+        quote! {
+            || -> usize { #fixed #(+ #dynamic)* }
+        }
+    }
+
+    fn add_nodes(&mut self, nodes: &[parse::HtmlNode], mut dynamic: bool) {
+        for node in nodes {
+            match node {
+                parse::HtmlNode::Arbitrary(_, expr) if dynamic && is_pure(expr) => {
+                    self.dynamic
+                        .push(quote!(::minihtml::ToHtmlNode::size_hint(&(#expr))));
+                }
+                parse::HtmlNode::Element(element) => self.add_element(element, dynamic),
+                parse::HtmlNode::Let(..) => dynamic = false,
+                _ => {}
+            }
+        }
+    }
+
+    fn add_element(&mut self, element: &parse::HtmlElement, dynamic: bool) {
+        let name = element.name.as_ref().len();
+        // `<name`
+        self.fixed += 1 + name;
+
+        if let Some(id) = &element.id {
+            // ` id="value"`
+            self.fixed += 6 + id.name.as_ref().len();
+        }
+        if !element.classes.is_empty() {
+            // ` class="a b"`
+            self.fixed += 8 + element
+                .classes
+                .iter()
+                .map(|class| class.name.as_ref().len() + 1)
+                .sum::<usize>();
+        }
+        for attr in element.attributes.iter().flat_map(|(_, attrs)| attrs) {
+            if let parse::Attribute::Static(attr) = attr {
+                // ` name`
+                self.fixed += 1 + attr.name.as_ref().len();
+                if let Some((_, value)) = &attr.value {
+                    // `="value"`
+                    self.fixed += 3;
+                    if let syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(lit),
+                        ..
+                    }) = value
+                    {
+                        self.fixed += lit.value().len();
+                    }
+                }
+            }
+        }
+
+        match &element.children {
+            Some(children) => {
+                // `>` and `</name>`
+                self.fixed += 4 + name;
+                // raw text nodes do not implement `ToHtmlNode`
+                self.add_nodes(&children.nodes, dynamic && !element.is_raw_text());
+            }
+            // `></name>`
+            None if element.is_raw_text() => self.fixed += 4 + name,
+            // `/>`
+            None => self.fixed += 2,
+        }
+    }
+}
+
+/// Checks whether the expression is a literal or a plain identifier, possibly borrowed.
+///
+/// The hint evaluates the expression a second time, so anything that could run user code, such
+/// as field access through `Deref`, is rejected.
+fn is_pure(expr: &syn::Expr) -> bool {
+    match expr {
+        syn::Expr::Lit(_) => true,
+        syn::Expr::Path(path) => path.qself.is_none() && path.path.get_ident().is_some(),
+        syn::Expr::Paren(paren) => is_pure(&paren.expr),
+        syn::Expr::Reference(reference) => is_pure(&reference.expr),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_quote::quote;
+
+    use super::*;
+
+    fn hint(input: TokenStream) -> (usize, usize) {
+        let nodes = syn::parse2::<parse::HtmlNodes>(input).unwrap();
+        let hint = Hint::of_nodes(&nodes.nodes);
+        (hint.fixed, hint.dynamic.len())
+    }
+
+    #[test]
+    fn hint_static() {
+        let hash = quote![#];
+        // `<div id="x" class="a bc" title="ab"><br/></div>`
+        assert_eq!(
+            hint(quote!(div.a.bc #hash x(title = "ab") { br; })),
+            (47, 0)
+        );
+    }
+
+    #[test]
+    fn hint_dynamic() {
+        assert_eq!(
+            hint(quote!(+a; +&(b); +"x"; +*c; +d.e; +f(); +a.b())),
+            (0, 3)
+        );
+    }
+
+    #[test]
+    fn hint_after_let() {
+        assert_eq!(hint(quote!(p { @let a = b; +a } +a)), (7, 1));
+    }
+
+    #[test]
+    fn hint_control_flow() {
+        assert_eq!(hint(quote!(@if a { br; +a })), (0, 0));
+    }
+}
@@ -1,13 +1,18 @@
 extern crate proc_macro as pm1;
 
-use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt;
 
+use minihtml_attrs::{is_event_handler, is_url_attr};
 use proc_macro2::TokenStream;
 use proc_quote::quote;
 use syn::spanned::Spanned;
 
+mod hint;
 mod parse;
+mod piece;
+
+use piece::Pieces;
 
 #[proc_macro_hack::proc_macro_hack]
 pub fn html(input: pm1::TokenStream) -> pm1::TokenStream {
@@ -16,104 +21,464 @@ pub fn html(input: pm1::TokenStream) -> pm1::TokenStream {
         .into()
 }
 
+#[proc_macro_hack::proc_macro_hack]
+pub fn try_html(input: pm1::TokenStream) -> pm1::TokenStream {
+    try_html_impl(input.into())
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+#[proc_macro_hack::proc_macro_hack]
+pub fn render_async(input: pm1::TokenStream) -> pm1::TokenStream {
+    render_async_impl(input.into())
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
 fn ctx<D: fmt::Display>(d: D) -> impl Fn(syn::Error) -> syn::Error {
     move |err| syn::Error::new(err.span(), format!("{}: {}", &d, err))
 }
 
 fn html_impl(input: TokenStream) -> syn::Result<TokenStream> {
-    let nodes = syn::parse2::<parse::HtmlNodes>(input).map_err(ctx("Parsing HTML input"))?;
-    let nodes = nodes
-        .nodes
-        .into_iter()
-        .map(write_node)
-        .collect::<syn::Result<Vec<TokenStream>>>()?;
+    let parse::HtmlInput { policy, nodes } =
+        syn::parse2(input).map_err(ctx("Parsing HTML input"))?;
+    let hint = hint::Hint::of_nodes(&nodes.nodes).to_closure();
+    let policy = policy.to_bindings();
+    let nodes = write_nodes(nodes, Mode::Sync)?.into_tokens();
     let result = quote! {{
-        let x = |output: &mut ::std::fmt::Formatter| -> ::std::fmt::Result {
+        let x = |output: &mut dyn ::std::fmt::Write| -> ::std::fmt::Result {
             use ::std::fmt;
-            use ::std::write;
+            #policy
 
-            #(#nodes)*
+            #nodes
             Ok(())
         };
 
-        ::minihtml::Html(x)
+        ::minihtml::Html(x, #hint)
     }};
     Ok(result)
 }
 
-fn write_node(node: parse::HtmlNode) -> syn::Result<TokenStream> {
-    Ok(match node {
-        parse::HtmlNode::Arbitrary(_, expr) => {
-            quote! {
-                ::minihtml::ToHtmlNode::fmt(#expr, output)?;
+fn try_html_impl(input: TokenStream) -> syn::Result<TokenStream> {
+    let parse::HtmlInput { policy, nodes } =
+        syn::parse2(input).map_err(ctx("Parsing HTML input"))?;
+    let hint = hint::Hint::of_nodes(&nodes.nodes).to_closure();
+    let policy = policy.to_bindings();
+    let nodes = write_nodes(nodes, Mode::Sync)?.into_tokens();
+    let result = quote! {{
+        let x = |output: &mut dyn ::std::fmt::Write| -> ::std::result::Result<(), _> {
+            use ::std::fmt;
+            #policy
+
+            #nodes
+            Ok(())
+        };
+
+        ::minihtml::TryHtml(x, #hint)
+    }};
+    Ok(result)
+}
+
+fn render_async_impl(input: TokenStream) -> syn::Result<TokenStream> {
+    let parse::AsyncInput {
+        writer,
+        policy,
+        nodes,
+    } = syn::parse2(input).map_err(ctx("Parsing HTML input"))?;
+    let policy = policy.to_bindings();
+    let nodes = write_nodes(nodes, Mode::Async)?.into_tokens();
+    let result = quote! {
+        async {
+            let mut output = ::minihtml::AsyncOutput::new(#writer);
+            let result = async {
+                let output = &mut output;
+                use ::std::fmt;
+                use ::std::fmt::Write as _;
+                #policy
+
+                #nodes
+                ::std::result::Result::<(), ::std::fmt::Error>::Ok(())
             }
+            .await;
+            output.finish(result).await
+        }
+    };
+    Ok(result)
+}
+
+/// The kind of function that the nodes are written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// A closure that writes into `&mut dyn fmt::Write`.
+    Sync,
+    /// An async block that writes into `&mut minihtml::AsyncOutput<W>`,
+    /// where nodes containing `.await` flush the output before they are evaluated.
+    Async,
+}
+
+fn write_nodes(nodes: parse::HtmlNodes, mode: Mode) -> syn::Result<Pieces> {
+    let mut pieces = Pieces::default();
+    for node in nodes.nodes {
+        pieces.extend(write_node(node, mode)?);
+    }
+    Ok(pieces)
+}
+
+fn write_node(node: parse::HtmlNode, mode: Mode) -> syn::Result<Pieces> {
+    let mut pieces = Pieces::default();
+    match node {
+        parse::HtmlNode::Arbitrary(_, expr) if mode == Mode::Async && contains_await(&expr) => {
+            pieces.push_code(quote! {
+                output.flush().await?;
+                {
+                    let node = #expr;
+                    ::minihtml::ToHtmlNode::fmt(&node, output)?;
+                }
+            });
+        }
+        parse::HtmlNode::Arbitrary(
+            _,
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(lit),
+                ..
+            }),
+        ) => {
+            pieces.push_text(&lit.value());
+        }
+        parse::HtmlNode::Arbitrary(_, expr) => {
+            pieces.push_code(quote! {
+                ::minihtml::ToHtmlNode::fmt(&(#expr), output)?;
+            });
         }
         parse::HtmlNode::Element(element) => {
             let element_name = element.name.as_ref();
-            let write_attrs = write_el_attrs(&element)?;
-            let write_child = match element.children {
+            pieces.push_static("<");
+            pieces.push_static(element_name);
+            pieces.extend(write_el_attrs(&element)?);
+            match element.children {
+                Some(inner_nodes) if element.is_raw_text() => {
+                    pieces.push_static(">");
+                    pieces.extend(write_raw_text(inner_nodes, element_name, mode)?);
+                    pieces.push_static("</");
+                    pieces.push_static(element_name);
+                    pieces.push_static(">");
+                }
                 Some(inner_nodes) => {
-                    let inner_nodes = inner_nodes
+                    // bindings from @let must not leak out of the element
+                    let scoped = inner_nodes
                         .nodes
-                        .into_iter()
-                        .map(write_node)
-                        .collect::<syn::Result<Vec<_>>>()?;
-                    quote! {
-                        write!(output, ">")?;
-                        #(#inner_nodes)*
-                        write!(output, concat!("</", #element_name, ">"))?;
+                        .iter()
+                        .any(|node| matches!(node, parse::HtmlNode::Let(..)));
+                    let inner_nodes = write_nodes(inner_nodes, mode)?;
+                    pieces.push_static(">");
+                    if scoped {
+                        let inner_nodes = inner_nodes.into_tokens();
+                        pieces.push_code(quote!({ #inner_nodes }));
+                    } else {
+                        pieces.extend(inner_nodes);
                     }
+                    pieces.push_static("</");
+                    pieces.push_static(element_name);
+                    pieces.push_static(">");
                 }
-                None => quote! {
-                    write!(output, "/>")?;
-                },
-            };
-            quote! {
-                write!(output, concat!("<", #element_name))?;
-                #write_attrs
-                #write_child
+                // raw text elements cannot be self-closing
+                None if element.is_raw_text() => {
+                    pieces.push_static("></");
+                    pieces.push_static(element_name);
+                    pieces.push_static(">");
+                }
+                None => pieces.push_static("/>"),
             }
         }
+        parse::HtmlNode::If(_, if_) => pieces.push_code(write_if(if_, mode)?),
+        parse::HtmlNode::For(_, for_) => {
+            let parse::HtmlFor {
+                for_,
+                pat,
+                in_,
+                expr,
+                body,
+                ..
+            } = for_;
+            let body = write_nodes(body, mode)?.into_tokens();
+            pieces.push_code(quote! {
+                #for_ #pat #in_ #expr {
+                    #body
+                }
+            });
+        }
+        parse::HtmlNode::Let(_, let_) => {
+            let parse::HtmlLet {
+                let_,
+                pat,
+                eq,
+                expr,
+                semi,
+            } = let_;
+            pieces.push_code(quote!(#let_ #pat #eq #expr #semi));
+        }
+        parse::HtmlNode::Component(_, component) => {
+            pieces.push_code(write_component(component)?);
+        }
+        parse::HtmlNode::Slot(at, slot) => {
+            return Err(syn::Error::new(
+                at.span().join(slot.span()).unwrap_or_else(|| slot.span()),
+                "@slot is only allowed directly inside component children",
+            ))
+        }
+        parse::HtmlNode::Match(_, match_) => {
+            let match_token = &match_.match_;
+            let expr = &match_.expr;
+            let arms = match_
+                .arms
+                .into_iter()
+                .map(|arm| {
+                    let pat = &arm.pat;
+                    let guard = arm.guard.as_ref().map(|(if_, guard)| quote!(#if_ #guard));
+                    let fat_arrow = &arm.fat_arrow;
+                    let body = write_nodes(arm.body, mode)?.into_tokens();
+                    Ok(quote! {
+                        #pat #guard #fat_arrow {
+                            #body
+                        }
+                    })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            pieces.push_code(quote! {
+                #match_token #expr {
+                    #(#arms)*
+                }
+            });
+        }
+    }
+    Ok(pieces)
+}
+
+/// Writes the content of a raw text element (`script` or `style`).
+///
+/// Only `+` nodes are allowed, and their values must implement `ToRawTextNode` instead of
+/// `ToHtmlNode`, because browsers do not unescape the content of raw text elements.
+fn write_raw_text(nodes: parse::HtmlNodes, element_name: &str, mode: Mode) -> syn::Result<Pieces> {
+    let mut pieces = Pieces::default();
+    for node in nodes.nodes {
+        match node {
+            parse::HtmlNode::Arbitrary(
+                _,
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(lit),
+                    ..
+                }),
+            ) => {
+                let value = lit.value();
+                if value.contains("</") || value.contains("<!--") {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        format!(
+                            "Literal content of <{}> must not contain \"</\" or \"<!--\"",
+                            element_name
+                        ),
+                    ));
+                }
+                pieces.push_static(&value);
+            }
+            parse::HtmlNode::Arbitrary(_, expr) if mode == Mode::Async && contains_await(&expr) => {
+                pieces.push_code(quote! {
+                    output.flush().await?;
+                    {
+                        let node = #expr;
+                        ::minihtml::ToRawTextNode::fmt(&node, output)?;
+                    }
+                });
+            }
+            parse::HtmlNode::Arbitrary(_, expr) => {
+                pieces.push_code(quote! {
+                    ::minihtml::ToRawTextNode::fmt(&(#expr), output)?;
+                });
+            }
+            node => {
+                return Err(syn::Error::new(
+                    node.span(),
+                    format!("Only `+` nodes are allowed inside <{}>", element_name),
+                ))
+            }
+        }
+    }
+    Ok(pieces)
+}
+
+fn write_component(component: parse::HtmlComponent) -> syn::Result<TokenStream> {
+    let path = &component.path;
+    let mut fields = HashSet::new();
+    let mut props = vec![];
+    for prop in component.props.iter().flat_map(|(_, props)| props) {
+        let name = &prop.name;
+        if !fields.insert(name.to_string()) {
+            return Err(syn::Error::new(
+                prop.span(),
+                format!("Duplicate prop \"{}\"", name),
+            ));
+        }
+        props.push(match &prop.value {
+            Some((_, value)) => quote!(#name: #value),
+            None => quote!(#name),
+        });
+    }
+
+    let mut children = vec![];
+    for node in component.children.into_iter().flat_map(|nodes| nodes.nodes) {
+        match node {
+            parse::HtmlNode::Slot(_, slot) => {
+                let name = &slot.name;
+                if !fields.insert(name.to_string()) {
+                    return Err(syn::Error::new(
+                        slot.span(),
+                        format!("Duplicate prop or slot \"{}\"", name),
+                    ));
+                }
+                let html = write_closure(slot.body.nodes)?;
+                props.push(quote!(#name: #html));
+            }
+            node => children.push(node),
+        }
+    }
+    let children = write_closure(children)?;
+
+    Ok(quote! {
+        ::minihtml::Component::fmt(
+            &#path { #(#props),* },
+            &#children,
+            output,
+        )?;
+    })
+}
+
+/// Writes the nodes into an `Html` value.
+fn write_closure(nodes: Vec<parse::HtmlNode>) -> syn::Result<TokenStream> {
+    let hint = hint::Hint::of_nodes(&nodes).to_closure();
+    let mut pieces = Pieces::default();
+    for node in nodes {
+        pieces.extend(write_node(node, Mode::Sync)?);
+    }
+    let nodes = pieces.into_tokens();
+    Ok(quote! {
+        ::minihtml::Html(
+            |output: &mut dyn ::std::fmt::Write| -> ::std::fmt::Result {
+                #nodes
+                Ok(())
+            },
+            #hint,
+        )
     })
 }
 
-fn write_el_attrs(element: &parse::HtmlElement) -> syn::Result<TokenStream> {
-    let mut static_attrs = HashMap::new();
-    let mut dyn_attrs = vec![];
-    for attr in element.attributes.iter().flat_map(|(_, attr)| attr) {
+fn write_if(if_: parse::HtmlIf, mode: Mode) -> syn::Result<TokenStream> {
+    let if_token = &if_.if_;
+    let cond = &if_.cond;
+    let then_branch = write_nodes(if_.then_branch, mode)?.into_tokens();
+    let else_branch = match if_.else_branch {
+        Some((_, _, parse::HtmlElse::If(else_if))) => {
+            let else_if = write_if(*else_if, mode)?;
+            quote!(else #else_if)
+        }
+        Some((_, _, parse::HtmlElse::Block(_, nodes))) => {
+            let nodes = write_nodes(nodes, mode)?.into_tokens();
+            quote!(else { #nodes })
+        }
+        None => quote!(),
+    };
+    Ok(quote! {
+        #if_token #cond {
+            #then_branch
+        } #else_branch
+    })
+}
+
+/// Writes the attributes of an element.
+///
+/// The `#id` shorthand is written first, followed by the `.class` shorthand,
+/// followed by the parenthesized attributes in source order.
+/// An explicit `class` attribute is merged into the `.class` shorthand position if both exist.
+fn write_el_attrs(element: &parse::HtmlElement) -> syn::Result<Pieces> {
+    enum Entry {
+        Static(String, AttrValue),
+        /// The index of the `dyn` attribute, whose name and value are bound in advance.
+        Dyn(usize),
+    }
+
+    let binding = |name: &str, index: usize| {
+        syn::Ident::new(
+            &format!("__minihtml_{}_{}", name, index),
+            proc_macro2::Span::call_site(),
+        )
+    };
+
+    let attrs = || element.attributes.iter().flat_map(|(_, attr)| attr);
+    // With `dyn` attributes, all names and values are evaluated in source order
+    // before any attribute is written, so that duplicates can be detected in advance.
+    let has_dyn = attrs().any(|attr| matches!(attr, parse::Attribute::Dyn(_)));
+    let mut bindings = vec![];
+    let mut dyn_count = 0;
+
+    let mut entries = vec![];
+    let mut static_names = HashSet::new();
+    for attr in attrs() {
         match attr {
             parse::Attribute::Static(attr) => {
-                if static_attrs.contains_key(attr.name.as_ref()) {
+                if !static_names.insert(attr.name.as_ref().to_string()) {
                     return Err(syn::Error::new(
                         attr.span(),
-                        &format!("Duplicate attribute \"{}\"", &attr.name.name),
-                    ))?;
+                        format!("Duplicate attribute \"{}\"", &attr.name.name),
+                    ));
                 }
-                static_attrs.insert(
-                    attr.name.as_ref().to_string(),
-                    attr.value
-                        .as_ref()
-                        .map_or_else(|| quote!(true), |(_, expr)| quote!(#expr)),
-                );
+                let mut value = attr
+                    .value
+                    .as_ref()
+                    .map_or(AttrValue::Flag(true), |(_, expr)| {
+                        AttrValue::from_expr(expr)
+                    });
+                if let (true, AttrValue::Expr(expr)) = (has_dyn, &mut value) {
+                    let value_binding = binding("attr", bindings.len());
+                    bindings.push(quote!(let #value_binding = &(#expr);));
+                    *expr = quote!((*#value_binding));
+                }
+                entries.push(Entry::Static(attr.name.as_ref().to_string(), value));
             }
             parse::Attribute::Dyn(attr) => {
-                dyn_attrs.push(attr);
+                let name = &attr.name;
+                let value = match &attr.value {
+                    Some((_, value)) => quote!(#value),
+                    None => quote!(true),
+                };
+                let (name_binding, value_binding) = (
+                    binding("dyn_name", dyn_count),
+                    binding("dyn_value", dyn_count),
+                );
+                bindings.push(quote! {
+                    let #name_binding = &(#name);
+                    let #value_binding = &(#value);
+                });
+                entries.push(Entry::Dyn(dyn_count));
+                dyn_count += 1;
             }
         }
     }
 
+    let mut shorthands = vec![];
+
     if let Some(id) = &element.id {
-        if static_attrs.contains_key("id") {
+        if !static_names.insert("id".to_string()) {
             return Err(syn::Error::new(
                 id.span(),
                 "Duplicate definition of attribute \"id\"",
             ));
         }
-        let id = id.name.as_ref();
-        static_attrs.insert("id".to_string(), quote!(#id));
+        shorthands.push(Entry::Static(
+            "id".to_string(),
+            AttrValue::Literal(id.name.as_ref().to_string()),
+        ));
     }
 
-    if element.classes.len() > 0 {
+    if !element.classes.is_empty() {
         use itertools::Itertools;
 
         let static_classes_joined = element
@@ -121,54 +486,157 @@ fn write_el_attrs(element: &parse::HtmlElement) -> syn::Result<TokenStream> {
             .iter()
             .map(|class| class.name.as_ref())
             .join(" ");
-        if static_attrs.contains_key("class") {
-            let dy = static_attrs
-                .get_mut("class")
-                .expect("Checked in the condition above");
-            *dy = quote! {
-                ::minihtml::hc::ClassConcat(#dy, #static_classes_joined)
-            };
-        } else {
-            static_attrs.insert(
-                "class".to_string(),
-                quote! {
-                    ::minihtml::NoSpecial(#static_classes_joined)
-                },
-            );
-        }
+        let explicit = entries.iter().position(|entry| match entry {
+            Entry::Static(name, _) => name == "class",
+            Entry::Dyn(_) => false,
+        });
+        let value = match explicit.map(|index| entries.remove(index)) {
+            Some(Entry::Static(_, AttrValue::Literal(dy))) if dy.is_empty() => {
+                AttrValue::Literal(static_classes_joined)
+            }
+            Some(Entry::Static(_, AttrValue::Literal(dy))) => {
+                AttrValue::Literal(format!("{} {}", static_classes_joined, dy))
+            }
+            Some(Entry::Static(_, dy)) => {
+                let dy = dy.to_tokens();
+                AttrValue::Expr(quote! {
+                    ::minihtml::hc::ClassConcat(&(#dy), #static_classes_joined)
+                })
+            }
+            _ => {
+                static_names.insert("class".to_string());
+                AttrValue::Literal(static_classes_joined)
+            }
+        };
+        shorthands.push(Entry::Static("class".to_string(), value));
     }
 
-    let attrs = static_attrs
-        .iter()
-        .map(|(name, value)| {
-            quote! {
+    let mut static_names = static_names.into_iter().collect::<Vec<_>>();
+    static_names.sort();
+
+    let mut pieces = Pieces::default();
+    for entry in shorthands.iter().chain(&entries) {
+        match entry {
+            Entry::Static(name, AttrValue::Literal(value)) => {
+                pieces.push_static(" ");
+                pieces.push_static(name);
+                pieces.push_static("=\"");
+                pieces.push_attr(value);
+                pieces.push_static("\"");
+            }
+            Entry::Static(name, AttrValue::Flag(true)) => {
+                pieces.push_static(" ");
+                pieces.push_static(name);
+            }
+            Entry::Static(_, AttrValue::Flag(false)) => {}
+            Entry::Static(name, AttrValue::Expr(value)) if is_event_handler(name) => pieces
+                .push_code(quote! {
+                    ::minihtml::ToScriptAttr::fmt(
+                        &(#value),
+                        ::minihtml::NoSpecial(#name),
+                        output
+                    )?;
+                }),
+            Entry::Static(name, AttrValue::Expr(value)) if is_url_attr(name) => {
+                pieces.push_code(quote! {
+                    ::minihtml::ToWholeHtmlAttr::fmt(
+                        &::minihtml::UrlAttr(&(#value)),
+                        ::minihtml::NoSpecial(#name),
+                        output
+                    )?;
+                })
+            }
+            Entry::Static(name, AttrValue::Expr(value)) => pieces.push_code(quote! {
                 ::minihtml::ToWholeHtmlAttr::fmt(
                     &(#value),
                     ::minihtml::NoSpecial(#name),
                     output
                 )?;
+            }),
+            &Entry::Dyn(index) => {
+                let value = binding("dyn_value", index);
+                pieces.push_code(quote! {
+                    if ::minihtml::hc::keep_dyn_attr(
+                        &__minihtml_dyn_names,
+                        #index,
+                        &[#(#static_names),*],
+                        __minihtml_duplicate_attr_policy,
+                    )? {
+                        if let Some(name) = ::minihtml::hc::dyn_attr_name(
+                            __minihtml_dyn_names[#index],
+                            #value,
+                            __minihtml_invalid_attr_policy,
+                        )? {
+                            ::minihtml::ToWholeHtmlAttr::fmt(#value, name, output)?;
+                        }
+                    }
+                });
             }
-        })
-        .chain(dyn_attrs.iter().map(|attr| {
-            let name = &attr.name;
-            let value = match &attr.value {
-                Some((_, value)) => quote!(#value),
-                None => quote!(true),
+        }
+    }
+
+    if !has_dyn {
+        return Ok(pieces);
+    }
+    let names = (0..dyn_count).map(|index| binding("dyn_name", index));
+    let attrs = pieces.into_tokens();
+    let mut scoped = Pieces::default();
+    scoped.push_code(quote! {{
+        #(#bindings)*
+        let __minihtml_dyn_names = [#(::std::convert::AsRef::<str>::as_ref(#names)),*];
+        #attrs
+    }});
+    Ok(scoped)
+}
+
+/// Checks whether the expression contains `.await` anywhere, e.g. `+fut.await?` or `+(x.await)`.
+fn contains_await(expr: &syn::Expr) -> bool {
+    fn scan(tokens: TokenStream) -> bool {
+        let mut after_dot = false;
+        tokens.into_iter().any(|token| {
+            let found = match &token {
+                proc_macro2::TokenTree::Ident(ident) => after_dot && ident == "await",
+                proc_macro2::TokenTree::Group(group) => scan(group.stream()),
+                _ => false,
             };
-            let static_names = static_attrs.iter().map(|(name, _)| name);
-            quote! {
-                let name = &(#name);
-                debug_assert!(match name {
-                    #(#static_names)|* => false,
-                    _ => true,
-                }, "The dynamic attribute {} duplicates a hardcoded attribute", name);
-                ::minihtml::ToWholeHtmlAttr::fmt(
-                    &(#value),
-                    ::minihtml::NoSpecial::debug_checked(name),
-                    output
-                )?;
-            }
-        }));
+            after_dot =
+                matches!(&token, proc_macro2::TokenTree::Punct(punct) if punct.as_char() == '.');
+            found
+        })
+    }
+    scan(quote!(#expr))
+}
 
-    Ok(quote!(#(#attrs)*))
+/// The value of a static attribute.
+enum AttrValue {
+    /// A string literal, unescaped.
+    Literal(String),
+    /// A boolean literal, or `true` if the value is omitted.
+    Flag(bool),
+    /// An expression implementing `ToWholeHtmlAttr`.
+    Expr(TokenStream),
+}
+
+impl AttrValue {
+    fn from_expr(expr: &syn::Expr) -> Self {
+        match expr {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(lit),
+                ..
+            }) => Self::Literal(lit.value()),
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Bool(lit),
+                ..
+            }) => Self::Flag(lit.value),
+            expr => Self::Expr(quote!(#expr)),
+        }
+    }
+
+    fn to_tokens(&self) -> TokenStream {
+        match self {
+            Self::Literal(value) => quote!(#value),
+            Self::Flag(value) => quote!(#value),
+            Self::Expr(expr) => expr.clone(),
+        }
+    }
 }
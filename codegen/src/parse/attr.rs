@@ -3,6 +3,7 @@ use syn::parse::{Parse, ParseStream};
 use syn::spanned::Spanned;
 
 #[cfg_attr(test, derive(Debug))]
+#[allow(clippy::large_enum_variant)]
 pub enum Attribute {
     Static(StaticAttribute),
     Dyn(DynAttribute),
@@ -66,11 +67,10 @@ pub struct DynAttribute {
 impl Parse for DynAttribute {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let dyn_ = input.parse()?;
-        let name = input.parse()?;
-        let value = if input.peek(syn::Token![=]) {
-            Some((input.parse()?, input.parse()?))
-        } else {
-            None
+        // `name = value` is parsed greedily as an assignment expression
+        let (name, value) = match input.parse()? {
+            syn::Expr::Assign(assign) => (*assign.left, Some((assign.eq_token, *assign.right))),
+            name => (name, None),
         };
         Ok(DynAttribute { dyn_, name, value })
     }
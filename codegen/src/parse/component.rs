@@ -0,0 +1,187 @@
+use proc_macro2::Span;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+
+use super::HtmlNodes;
+use crate::ctx;
+
+/// A component invocation, starting from the component path.
+///
+/// `Card(title = t) { children }` constructs `Card { title: t }` and passes the children to
+/// `minihtml::Component::fmt`.
+/// `@slot` blocks in the children are passed as additional fields of the props struct instead.
+pub struct HtmlComponent {
+    pub path: syn::Path,
+    pub props: Option<(syn::token::Paren, Punctuated<ComponentProp, syn::Token![,]>)>,
+    pub children: Option<HtmlNodes>,
+    span: Span,
+}
+
+impl Parse for HtmlComponent {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input
+            .call(syn::Path::parse_mod_style)
+            .map_err(ctx("Parsing component path"))?;
+        let mut span = path.span();
+
+        let props = if input.peek(syn::token::Paren) {
+            let inner;
+            let paren = syn::parenthesized!(inner in input);
+            span = span.join(paren.span).unwrap_or(span);
+            Some((
+                paren,
+                inner
+                    .parse_terminated(ComponentProp::parse)
+                    .map_err(ctx("Parsing component prop"))?,
+            ))
+        } else {
+            None
+        };
+
+        let children = if input.peek(syn::token::Brace) {
+            let inner;
+            let brace = syn::braced!(inner in input);
+            span = span.join(brace.span).unwrap_or(span);
+            Some(inner.parse().map_err(ctx("Parsing component children"))?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            path,
+            props,
+            children,
+            span,
+        })
+    }
+}
+
+impl Spanned for HtmlComponent {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// A prop passed to a component, in the form `name = value` or just `name`.
+pub struct ComponentProp {
+    pub name: syn::Ident,
+    pub value: Option<(syn::Token![=], syn::Expr)>,
+}
+
+impl Parse for ComponentProp {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        let value = if input.peek(syn::Token![=]) {
+            Some((input.parse().unwrap(), input.parse()?))
+        } else {
+            None
+        };
+        Ok(Self { name, value })
+    }
+}
+
+impl Spanned for ComponentProp {
+    fn span(&self) -> Span {
+        let mut span = self.name.span();
+        if let Some((eq, expr)) = &self.value {
+            span = span.join(eq.span()).unwrap_or(span);
+            span = span.join(expr.span()).unwrap_or(span);
+        }
+        span
+    }
+}
+
+/// A named slot block inside component children, starting from the `slot` identifier.
+pub struct HtmlSlot {
+    pub name: syn::Ident,
+    pub body: HtmlNodes,
+    span: Span,
+}
+
+impl HtmlSlot {
+    /// Checks whether the input starts with `slot name`.
+    pub fn peek(input: ParseStream) -> bool {
+        let fork = input.fork();
+        match fork.parse::<syn::Ident>() {
+            Ok(ident) => ident == "slot" && fork.peek(syn::Ident),
+            Err(_) => false,
+        }
+    }
+}
+
+impl Parse for HtmlSlot {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let slot: syn::Ident = input.parse()?;
+        if slot != "slot" {
+            return Err(syn::Error::new(slot.span(), "Expected `slot`"));
+        }
+        let name = input.parse().map_err(ctx("Parsing slot name"))?;
+        let inner;
+        let brace = syn::braced!(inner in input);
+        let body = inner.parse().map_err(ctx("Parsing slot contents"))?;
+        let span = slot.span().join(brace.span).unwrap_or_else(|| slot.span());
+        Ok(Self { name, body, span })
+    }
+}
+
+impl Spanned for HtmlSlot {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_quote::quote;
+
+    use super::*;
+
+    #[test]
+    fn parse_component() {
+        let parsed = syn::parse2::<HtmlComponent>(quote! {
+            ui::Card(title = "x", body) { +"child" }
+        })
+        .unwrap();
+        let path = parsed.path;
+        assert_eq!(quote!(#path).to_string(), quote!(ui::Card).to_string());
+        let (_, props) = parsed.props.unwrap();
+        let props = props.into_iter().collect::<Vec<_>>();
+        assert_eq!(props.len(), 2);
+        assert_eq!(props[0].name.to_string(), "title");
+        assert!(props[0].value.is_some());
+        assert_eq!(props[1].name.to_string(), "body");
+        assert!(props[1].value.is_none());
+        assert_eq!(parsed.children.unwrap().nodes.len(), 1);
+    }
+
+    #[test]
+    fn parse_component_bare() {
+        let parsed = syn::parse2::<HtmlComponent>(quote!(Separator)).unwrap();
+        assert!(parsed.props.is_none());
+        assert!(parsed.children.is_none());
+    }
+
+    #[test]
+    fn parse_slot() {
+        let input = quote!(slot sidebar { a; b; });
+        let peeked = syn::parse::Parser::parse2(
+            |input: ParseStream| {
+                let peeked = HtmlSlot::peek(input);
+                input.parse::<proc_macro2::TokenStream>()?;
+                Ok(peeked)
+            },
+            input.clone(),
+        )
+        .unwrap();
+        assert!(peeked);
+        let parsed = syn::parse2::<HtmlSlot>(input).unwrap();
+        assert_eq!(parsed.name.to_string(), "sidebar");
+        assert_eq!(parsed.body.nodes.len(), 2);
+    }
+
+    #[test]
+    fn parse_slot_without_name() {
+        assert!(syn::parse2::<HtmlSlot>(quote!(slot { a; })).is_err());
+    }
+}
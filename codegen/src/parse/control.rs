@@ -0,0 +1,399 @@
+use proc_macro2::Span;
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+
+use super::HtmlNodes;
+use crate::ctx;
+
+fn parse_block(input: ParseStream) -> syn::Result<(syn::token::Brace, HtmlNodes)> {
+    let inner;
+    let brace = syn::braced!(inner in input);
+    let nodes = inner.parse().map_err(ctx("Parsing block contents"))?;
+    Ok((brace, nodes))
+}
+
+/// Parses a pattern that may contain top-level `|` alternatives.
+fn parse_multi_pat(input: ParseStream) -> syn::Result<syn::Pat> {
+    let leading_vert: Option<syn::Token![|]> = input.parse()?;
+    let pat: syn::Pat = input.parse()?;
+    let is_vert = |input: ParseStream| {
+        input.peek(syn::Token![|]) && !input.peek(syn::Token![||]) && !input.peek(syn::Token![|=])
+    };
+    if leading_vert.is_none() && !is_vert(input) {
+        return Ok(pat);
+    }
+
+    let mut cases = syn::punctuated::Punctuated::new();
+    cases.push_value(pat);
+    while is_vert(input) {
+        cases.push_punct(input.parse()?);
+        cases.push_value(input.parse()?);
+    }
+    Ok(syn::Pat::Or(syn::PatOr {
+        attrs: vec![],
+        leading_vert,
+        cases,
+    }))
+}
+
+/// An `if` node, starting from the `if` keyword.
+///
+/// `if let` conditions are supported as in Rust.
+pub struct HtmlIf {
+    pub if_: syn::Token![if],
+    pub cond: syn::Expr,
+    pub then_branch: HtmlNodes,
+    pub else_branch: Option<(syn::Token![@], syn::Token![else], HtmlElse)>,
+    span: Span,
+}
+
+impl Parse for HtmlIf {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let if_: syn::Token![if] = input.parse()?;
+        let cond = input
+            .call(syn::Expr::parse_without_eager_brace)
+            .map_err(ctx("Parsing if condition"))?;
+        let (brace, then_branch) = parse_block(input)?;
+        let mut span = if_.span().join(brace.span).unwrap_or_else(|| if_.span());
+
+        let else_branch = if input.peek(syn::Token![@]) && input.peek2(syn::Token![else]) {
+            let at: syn::Token![@] = input.parse().unwrap();
+            let else_: syn::Token![else] = input.parse().unwrap();
+            let branch: HtmlElse = input.parse().map_err(ctx("Parsing else branch"))?;
+            span = span.join(branch.span()).unwrap_or(span);
+            Some((at, else_, branch))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            if_,
+            cond,
+            then_branch,
+            else_branch,
+            span,
+        })
+    }
+}
+
+impl Spanned for HtmlIf {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// The part following `@else`.
+pub enum HtmlElse {
+    If(Box<HtmlIf>),
+    Block(syn::token::Brace, HtmlNodes),
+}
+
+impl Parse for HtmlElse {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::Token![if]) {
+            Ok(Self::If(Box::new(input.parse()?)))
+        } else {
+            let (brace, nodes) = parse_block(input)?;
+            Ok(Self::Block(brace, nodes))
+        }
+    }
+}
+
+impl Spanned for HtmlElse {
+    fn span(&self) -> Span {
+        match self {
+            Self::If(if_) => if_.span(),
+            Self::Block(brace, _) => brace.span,
+        }
+    }
+}
+
+/// A `for` node, starting from the `for` keyword.
+pub struct HtmlFor {
+    pub for_: syn::Token![for],
+    pub pat: syn::Pat,
+    pub in_: syn::Token![in],
+    pub expr: syn::Expr,
+    pub body: HtmlNodes,
+    span: Span,
+}
+
+impl Parse for HtmlFor {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let for_: syn::Token![for] = input.parse()?;
+        let pat = parse_multi_pat(input).map_err(ctx("Parsing for loop pattern"))?;
+        let in_ = input.parse()?;
+        let expr = input
+            .call(syn::Expr::parse_without_eager_brace)
+            .map_err(ctx("Parsing for loop iterator"))?;
+        let (brace, body) = parse_block(input)?;
+        let span = for_.span().join(brace.span).unwrap_or_else(|| for_.span());
+        Ok(Self {
+            for_,
+            pat,
+            in_,
+            expr,
+            body,
+            span,
+        })
+    }
+}
+
+impl Spanned for HtmlFor {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// A `match` node, starting from the `match` keyword.
+pub struct HtmlMatch {
+    pub match_: syn::Token![match],
+    pub expr: syn::Expr,
+    pub brace: syn::token::Brace,
+    pub arms: Vec<HtmlArm>,
+}
+
+impl Parse for HtmlMatch {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let match_ = input.parse()?;
+        let expr = input
+            .call(syn::Expr::parse_without_eager_brace)
+            .map_err(ctx("Parsing match scrutinee"))?;
+        let inner;
+        let brace = syn::braced!(inner in input);
+        let mut arms = vec![];
+        while !inner.is_empty() {
+            arms.push(inner.parse().map_err(ctx("Parsing match arm"))?);
+        }
+        Ok(Self {
+            match_,
+            expr,
+            brace,
+            arms,
+        })
+    }
+}
+
+impl Spanned for HtmlMatch {
+    fn span(&self) -> Span {
+        self.match_
+            .span()
+            .join(self.brace.span)
+            .unwrap_or_else(|| self.match_.span())
+    }
+}
+
+/// An arm in a `match` node, in the form `Pat if guard => { nodes }`.
+pub struct HtmlArm {
+    pub pat: syn::Pat,
+    pub guard: Option<(syn::Token![if], syn::Expr)>,
+    pub fat_arrow: syn::Token![=>],
+    pub body: HtmlNodes,
+    span: Span,
+}
+
+impl Parse for HtmlArm {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let pat = parse_multi_pat(input).map_err(ctx("Parsing match arm pattern"))?;
+        let mut span = pat.span();
+        let guard = if input.peek(syn::Token![if]) {
+            let if_ = input.parse().unwrap();
+            let guard = input.parse().map_err(ctx("Parsing match arm guard"))?;
+            Some((if_, guard))
+        } else {
+            None
+        };
+        let fat_arrow = input.parse()?;
+        if !input.peek(syn::token::Brace) {
+            return Err(input.error("Expected a braced block of nodes as the match arm body"));
+        }
+        let (brace, body) = parse_block(input)?;
+        span = span.join(brace.span).unwrap_or(span);
+        if input.peek(syn::Token![,]) {
+            input.parse::<syn::Token![,]>().unwrap();
+        }
+        Ok(Self {
+            pat,
+            guard,
+            fat_arrow,
+            body,
+            span,
+        })
+    }
+}
+
+impl Spanned for HtmlArm {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// A `let` node, starting from the `let` keyword.
+///
+/// The binding is visible to the remaining sibling nodes.
+pub struct HtmlLet {
+    pub let_: syn::Token![let],
+    pub pat: syn::Pat,
+    pub eq: syn::Token![=],
+    pub expr: syn::Expr,
+    pub semi: syn::Token![;],
+}
+
+impl Parse for HtmlLet {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let let_ = input.parse()?;
+        let mut pat = input.parse().map_err(ctx("Parsing let pattern"))?;
+        if input.peek(syn::Token![:]) {
+            pat = syn::Pat::Type(syn::PatType {
+                attrs: vec![],
+                pat: Box::new(pat),
+                colon_token: input.parse().unwrap(),
+                ty: input.parse().map_err(ctx("Parsing let type"))?,
+            });
+        }
+        let eq = input.parse()?;
+        let expr = input.parse().map_err(ctx("Parsing let expression"))?;
+        let semi = input.parse()?;
+        Ok(Self {
+            let_,
+            pat,
+            eq,
+            expr,
+            semi,
+        })
+    }
+}
+
+impl_span!(HtmlLet = pat << let_ >> semi);
+
+#[cfg(test)]
+mod tests {
+    use proc_quote::quote;
+
+    use super::*;
+
+    #[test]
+    fn parse_if() {
+        let parsed = syn::parse2::<HtmlIf>(quote! {
+            if a == b { +"x" }
+        })
+        .unwrap();
+        let cond = parsed.cond;
+        assert_eq!(quote!(#cond).to_string(), quote!(a == b).to_string());
+        assert_eq!(parsed.then_branch.nodes.len(), 1);
+        assert!(parsed.else_branch.is_none());
+    }
+
+    #[test]
+    fn parse_if_let() {
+        let parsed = syn::parse2::<HtmlIf>(quote! {
+            if let Some(x) = y { +x }
+        })
+        .unwrap();
+        let cond = parsed.cond;
+        assert_eq!(
+            quote!(#cond).to_string(),
+            quote!(let Some(x) = y).to_string()
+        );
+    }
+
+    #[test]
+    fn parse_if_else_chain() {
+        let parsed = syn::parse2::<HtmlIf>(quote! {
+            if a { +"a" } @else if b { +"b" } @else { +"c" br; }
+        })
+        .unwrap();
+        let (_, _, else_if) = parsed.else_branch.unwrap();
+        let else_if = match else_if {
+            HtmlElse::If(x) => x,
+            _ => panic!("Expected else-if branch"),
+        };
+        let cond = else_if.cond;
+        assert_eq!(quote!(#cond).to_string(), quote!(b).to_string());
+        let (_, _, else_) = else_if.else_branch.unwrap();
+        let nodes = match else_ {
+            HtmlElse::Block(_, x) => x,
+            _ => panic!("Expected else block"),
+        };
+        assert_eq!(nodes.nodes.len(), 2);
+    }
+
+    #[test]
+    fn parse_if_without_block() {
+        assert!(syn::parse2::<HtmlIf>(quote!(if a)).is_err());
+    }
+
+    #[test]
+    fn parse_for() {
+        let parsed = syn::parse2::<HtmlFor>(quote! {
+            for (i, item) in items.iter().enumerate() { li { +item } }
+        })
+        .unwrap();
+        let pat = parsed.pat;
+        assert_eq!(quote!(#pat).to_string(), quote!((i, item)).to_string());
+        let expr = parsed.expr;
+        assert_eq!(
+            quote!(#expr).to_string(),
+            quote!(items.iter().enumerate()).to_string()
+        );
+        assert_eq!(parsed.body.nodes.len(), 1);
+    }
+
+    #[test]
+    fn parse_for_or_pattern() {
+        let parsed = syn::parse2::<HtmlFor>(quote! {
+            for Ok(x) | Err(x) in results {}
+        })
+        .unwrap();
+        assert!(matches!(parsed.pat, syn::Pat::Or(_)));
+    }
+
+    #[test]
+    fn parse_match() {
+        let parsed = syn::parse2::<HtmlMatch>(quote! {
+            match state {
+                State::A | State::B => { +"ab" }
+                State::C(x) if x > 0 => { +"c" br; },
+                _ => {}
+            }
+        })
+        .unwrap();
+        let expr = parsed.expr;
+        assert_eq!(quote!(#expr).to_string(), quote!(state).to_string());
+        assert_eq!(parsed.arms.len(), 3);
+        assert!(matches!(parsed.arms[0].pat, syn::Pat::Or(_)));
+        assert!(parsed.arms[0].guard.is_none());
+        let (_, guard) = parsed.arms[1].guard.as_ref().unwrap();
+        assert_eq!(quote!(#guard).to_string(), quote!(x > 0).to_string());
+        assert_eq!(parsed.arms[1].body.nodes.len(), 2);
+        assert_eq!(parsed.arms[2].body.nodes.len(), 0);
+    }
+
+    #[test]
+    fn parse_match_unbraced_arm() {
+        assert!(syn::parse2::<HtmlMatch>(quote! {
+            match state { _ => +"x" }
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn parse_let() {
+        let parsed = syn::parse2::<HtmlLet>(quote! {
+            let (a, b): (u32, &str) = compute();
+        })
+        .unwrap();
+        let pat = parsed.pat;
+        assert_eq!(
+            quote!(#pat).to_string(),
+            quote!((a, b): (u32, &str)).to_string()
+        );
+        let expr = parsed.expr;
+        assert_eq!(quote!(#expr).to_string(), quote!(compute()).to_string());
+    }
+
+    #[test]
+    fn parse_let_without_semi() {
+        assert!(syn::parse2::<HtmlLet>(quote!(let a = b)).is_err());
+    }
+}
@@ -85,6 +85,14 @@ impl Parse for HtmlElement {
     }
 }
 
+impl HtmlElement {
+    /// Whether this is a raw text element, whose content is not parsed as HTML by browsers.
+    pub fn is_raw_text(&self) -> bool {
+        let name = self.name.as_ref();
+        name.eq_ignore_ascii_case("script") || name.eq_ignore_ascii_case("style")
+    }
+}
+
 impl Spanned for HtmlElement {
     fn span(&self) -> Span {
         self.span
@@ -136,7 +144,7 @@ mod tests {
         assert_eq!(parsed.classes.len(), 0);
         assert!(parsed.id.is_none());
         assert!(parsed.attributes.is_none());
-        assert_eq!(parsed.children.unwrap().nodes.into_iter().count(), 0);
+        assert_eq!(parsed.children.unwrap().nodes.len(), 0);
     }
 
     #[test]
@@ -149,13 +157,13 @@ mod tests {
         assert_eq!(parsed.classes.len(), 0);
         assert!(parsed.id.is_none());
         assert!(parsed.attributes.is_none());
-        assert_eq!(parsed.children.unwrap().nodes.into_iter().count(), 0);
+        assert_eq!(parsed.children.unwrap().nodes.len(), 0);
     }
 
     #[test]
     fn test_id() {
         let hash = quote![#];
-        #[cfg_attr(rustfmt, rustfmt_skip)]
+        #[rustfmt::skip]
         let parsed = syn::parse2::<HtmlElement>(quote! {
             foo #hash ab-c;
         })
@@ -169,7 +177,7 @@ mod tests {
 
     #[test]
     fn test_classes() {
-        #[cfg_attr(rustfmt, rustfmt_skip)]
+        #[rustfmt::skip]
         let parsed = syn::parse2::<HtmlElement>(quote! {
             foo.de-f.gh-i;
         })
@@ -190,7 +198,7 @@ mod tests {
 
     #[test]
     fn test_classes_num() {
-        #[cfg_attr(rustfmt, rustfmt_skip)]
+        #[rustfmt::skip]
         let parsed = syn::parse2::<HtmlElement>(quote! {
             foo.de-f0.gh-i;
         })
@@ -212,7 +220,7 @@ mod tests {
     #[test]
     fn test_id_classes_mixed() {
         let hash = quote![#];
-        #[cfg_attr(rustfmt, rustfmt_skip)]
+        #[rustfmt::skip]
         let parsed = syn::parse2::<HtmlElement>(quote! {
             foo .de-f #hash ab-c .gh-i;
         })
@@ -34,6 +34,36 @@ pub use element::*;
 mod id_class;
 pub use id_class::*;
 
+mod control;
+pub use control::*;
+
+mod component;
+pub use component::*;
+
+mod policy;
+pub use policy::*;
+
+/// The input of `render_async!`, in the form `writer, #![attr_policy(...)] nodes...`.
+pub struct AsyncInput {
+    pub writer: syn::Expr,
+    pub policy: AttrPolicy,
+    pub nodes: HtmlNodes,
+}
+
+impl Parse for AsyncInput {
+    fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+        let writer = input.parse().map_err(ctx("Parsing writer expression"))?;
+        input.parse::<syn::Token![,]>()?;
+        let policy = input.parse()?;
+        let nodes = input.parse()?;
+        Ok(Self {
+            writer,
+            policy,
+            nodes,
+        })
+    }
+}
+
 pub struct HtmlNodes {
     pub nodes: Vec<HtmlNode>,
     span: Span,
@@ -63,6 +93,12 @@ impl Spanned for HtmlNodes {
 pub enum HtmlNode {
     Arbitrary(syn::Token![+], syn::Expr),
     Element(HtmlElement),
+    If(syn::Token![@], HtmlIf),
+    For(syn::Token![@], HtmlFor),
+    Match(syn::Token![@], HtmlMatch),
+    Let(syn::Token![@], HtmlLet),
+    Component(syn::Token![@], HtmlComponent),
+    Slot(syn::Token![@], HtmlSlot),
 }
 
 impl Parse for HtmlNode {
@@ -76,6 +112,28 @@ impl Parse for HtmlNode {
                 input.parse::<syn::Token![;]>().unwrap();
             }
             HtmlNode::Arbitrary(plus, expr)
+        } else if input.peek(syn::Token![@]) {
+            let at: syn::Token![@] = input.parse().unwrap();
+            let node = if input.peek(syn::Token![if]) {
+                HtmlNode::If(at, input.parse().map_err(ctx("Parsing @if node"))?)
+            } else if input.peek(syn::Token![for]) {
+                HtmlNode::For(at, input.parse().map_err(ctx("Parsing @for node"))?)
+            } else if input.peek(syn::Token![match]) {
+                HtmlNode::Match(at, input.parse().map_err(ctx("Parsing @match node"))?)
+            } else if input.peek(syn::Token![let]) {
+                HtmlNode::Let(at, input.parse().map_err(ctx("Parsing @let node"))?)
+            } else if HtmlSlot::peek(input) {
+                HtmlNode::Slot(at, input.parse().map_err(ctx("Parsing @slot node"))?)
+            } else if input.peek(syn::Ident) || input.peek(syn::Token![::]) {
+                HtmlNode::Component(at, input.parse().map_err(ctx("Parsing component node"))?)
+            } else {
+                return Err(input
+                    .error("Expected `if`, `for`, `match`, `let` or a component path after `@`"));
+            };
+            if input.peek(syn::Token![;]) {
+                input.parse::<syn::Token![;]>().unwrap();
+            }
+            node
         } else {
             HtmlNode::Element(input.parse().map_err(ctx("Parsing HTML element node"))?)
         };
@@ -92,6 +150,18 @@ impl Spanned for HtmlNode {
                 span
             }
             Self::Element(el) => el.span(),
+            Self::If(at, if_) => at.span().join(if_.span()).unwrap_or_else(|| if_.span()),
+            Self::For(at, for_) => at.span().join(for_.span()).unwrap_or_else(|| for_.span()),
+            Self::Let(at, let_) => at.span().join(let_.span()).unwrap_or_else(|| let_.span()),
+            Self::Component(at, component) => at
+                .span()
+                .join(component.span())
+                .unwrap_or_else(|| component.span()),
+            Self::Slot(at, slot) => at.span().join(slot.span()).unwrap_or_else(|| slot.span()),
+            Self::Match(at, match_) => at
+                .span()
+                .join(match_.span())
+                .unwrap_or_else(|| match_.span()),
         }
     }
 }
@@ -19,7 +19,7 @@ impl AsRef<str> for Hyphenated {
 
 impl PartialEq<Hyphenated> for Hyphenated {
     fn eq(&self, other: &Self) -> bool {
-        &self.name == &other.name
+        self.name == other.name
     }
 }
 
@@ -69,7 +69,7 @@ mod tests {
     }
 
     #[test]
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+    #[rustfmt::skip]
     fn parse_hyphenated_ident() {
         let parsed = syn::parse2::<Hyphenated>(quote!(abc - def-ghi)).unwrap();
         assert_eq!(parsed.name.as_str(), "abc-def-ghi");
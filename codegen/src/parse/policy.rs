@@ -0,0 +1,134 @@
+use proc_macro2::TokenStream;
+use proc_quote::quote;
+use syn::parse::{Parse, ParseStream};
+
+use crate::ctx;
+
+/// The policies for rejected and duplicate `dyn` attributes, given at the start of the input as
+/// `#![attr_policy(invalid = Skip, duplicate = LastWins)]`.
+///
+/// Omitted policies use the default variant.
+#[derive(Default)]
+pub struct AttrPolicy {
+    pub invalid: Option<syn::Ident>,
+    pub duplicate: Option<syn::Ident>,
+}
+
+impl AttrPolicy {
+    /// Binds the policies to the variables used by the code generated for `dyn` attributes.
+    pub fn to_bindings(&self) -> TokenStream {
+        let invalid = self
+            .invalid
+            .clone()
+            .unwrap_or_else(|| syn::Ident::new("Error", proc_macro2::Span::call_site()));
+        let duplicate = self
+            .duplicate
+            .clone()
+            .unwrap_or_else(|| syn::Ident::new("Error", proc_macro2::Span::call_site()));
+        quote! {
+            let __minihtml_invalid_attr_policy = ::minihtml::InvalidAttrPolicy::#invalid;
+            let __minihtml_duplicate_attr_policy = ::minihtml::DuplicateAttrPolicy::#duplicate;
+        }
+    }
+}
+
+impl Parse for AttrPolicy {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut policy = Self::default();
+        while input.peek(syn::Token![#]) && input.peek2(syn::Token![!]) {
+            input.parse::<syn::Token![#]>()?;
+            input.parse::<syn::Token![!]>()?;
+            let attr;
+            syn::bracketed!(attr in input);
+            let name: syn::Ident = attr.parse()?;
+            if name != "attr_policy" {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "Expected `attr_policy`, the only supported inner attribute",
+                ));
+            }
+            let args;
+            syn::parenthesized!(args in attr);
+            let args = args
+                .parse_terminated::<_, syn::Token![,]>(|input: ParseStream| {
+                    let key: syn::Ident = input.parse()?;
+                    input.parse::<syn::Token![=]>()?;
+                    let value: syn::Ident = input.parse()?;
+                    Ok((key, value))
+                })
+                .map_err(ctx("Parsing attr_policy"))?;
+            for (key, value) in args {
+                let (slot, variants) = if key == "invalid" {
+                    (&mut policy.invalid, &["Skip", "Error", "Panic"])
+                } else if key == "duplicate" {
+                    (&mut policy.duplicate, &["LastWins", "FirstWins", "Error"])
+                } else {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "Expected `invalid` or `duplicate`",
+                    ));
+                };
+                if !variants.iter().any(|variant| value == variant) {
+                    return Err(syn::Error::new(
+                        value.span(),
+                        format!("Expected one of {}", variants.join(", ")),
+                    ));
+                }
+                if slot.is_some() {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!("Duplicate `{}` policy", key),
+                    ));
+                }
+                *slot = Some(value);
+            }
+        }
+        Ok(policy)
+    }
+}
+
+/// The input of `html!` and `try_html!`, in the form `#![attr_policy(...)] nodes...`.
+pub struct HtmlInput {
+    pub policy: AttrPolicy,
+    pub nodes: super::HtmlNodes,
+}
+
+impl Parse for HtmlInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let policy = input.parse()?;
+        let nodes = input.parse()?;
+        Ok(Self { policy, nodes })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_quote::quote;
+
+    use super::*;
+
+    #[test]
+    fn parse_policy() {
+        let hash = quote![#];
+        let policy = syn::parse2::<AttrPolicy>(
+            quote!(#hash![attr_policy(invalid = Skip)] #hash![attr_policy(duplicate = LastWins,)]),
+        )
+        .unwrap();
+        assert_eq!(policy.invalid.unwrap(), "Skip");
+        assert_eq!(policy.duplicate.unwrap(), "LastWins");
+    }
+
+    #[test]
+    fn parse_policy_errors() {
+        let hash = quote![#];
+        assert!(
+            syn::parse2::<AttrPolicy>(quote!(#hash![attr_policy(invalid = LastWins)])).is_err()
+        );
+        assert!(syn::parse2::<AttrPolicy>(quote!(#hash![attr_policy(other = Skip)])).is_err());
+        assert!(syn::parse2::<AttrPolicy>(quote!(#hash![allow(invalid = Skip)])).is_err());
+        assert!(syn::parse2::<AttrPolicy>(
+            quote!(#hash![attr_policy(invalid = Skip, invalid = Panic)])
+        )
+        .is_err());
+    }
+}
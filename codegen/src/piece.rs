@@ -0,0 +1,135 @@
+use proc_macro2::TokenStream;
+use proc_quote::quote;
+
+/// A sequence of static strings and code that writes into `output`.
+///
+/// Consecutive static strings are merged into a single `write_str` call.
+#[derive(Default)]
+pub struct Pieces(Vec<Piece>);
+
+enum Piece {
+    Static(String),
+    Code(TokenStream),
+}
+
+impl Pieces {
+    /// Appends a string that is written verbatim.
+    pub fn push_static(&mut self, s: &str) {
+        if let Some(Piece::Static(last)) = self.0.last_mut() {
+            last.push_str(s);
+        } else {
+            self.0.push(Piece::Static(s.to_string()));
+        }
+    }
+
+    /// Appends a string that is escaped as text at compile time.
+    pub fn push_text(&mut self, s: &str) {
+        self.push_static(&escape(s, Context::Text));
+    }
+
+    /// Appends a string that is escaped as a double-quoted attribute value at compile time.
+    pub fn push_attr(&mut self, s: &str) {
+        self.push_static(&escape(s, Context::Attr));
+    }
+
+    /// Appends code that writes into `output`.
+    pub fn push_code(&mut self, code: TokenStream) {
+        self.0.push(Piece::Code(code));
+    }
+
+    /// Appends other pieces, merging the static strings at the boundary.
+    pub fn extend(&mut self, other: Pieces) {
+        for piece in other.0 {
+            match piece {
+                Piece::Static(s) => self.push_static(&s),
+                Piece::Code(code) => self.push_code(code),
+            }
+        }
+    }
+
+    /// Generates the statements that write the pieces.
+    pub fn into_tokens(self) -> TokenStream {
+        let stmts = self.0.into_iter().map(|piece| match piece {
+            Piece::Static(s) => quote!(output.write_str(#s)?;),
+            Piece::Code(code) => code,
+        });
+        quote!(#(#stmts)*)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Context {
+    Text,
+    Attr,
+}
+
+/// Escapes a string in the same way as `escape_text` or `escape_attr` does at runtime.
+fn escape(s: &str, context: Context) -> String {
+    let mut output = String::with_capacity(s.len());
+    for char in s.chars() {
+        let escape = match (char, context) {
+            ('&', _) => "&amp;",
+            ('<', Context::Text) => "&lt;",
+            ('"', Context::Attr) => "&quot;",
+            _ if cfg!(feature = "strict-escaping") => match char {
+                '<' => "&lt;",
+                '>' => "&gt;",
+                '\'' => "&apos;",
+                '"' => "&quot;",
+                _ => {
+                    output.push(char);
+                    continue;
+                }
+            },
+            _ => {
+                output.push(char);
+                continue;
+            }
+        };
+        output.push_str(escape);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_quote::quote;
+
+    use super::*;
+
+    #[test]
+    fn merge_static() {
+        let mut pieces = Pieces::default();
+        pieces.push_static("<a");
+        pieces.push_text(" & ");
+        pieces.push_code(quote!(foo();));
+        pieces.push_static(">");
+        let mut other = Pieces::default();
+        other.push_static("</a>");
+        other.push_code(quote!(bar();));
+        pieces.extend(other);
+        assert_eq!(
+            pieces.into_tokens().to_string(),
+            quote! {
+                output.write_str("<a &amp; ")?;
+                foo();
+                output.write_str("></a>")?;
+                bar();
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "strict-escaping"))]
+    fn escape_context() {
+        assert_eq!(
+            escape("<a href=\"x\">'&'", Context::Text),
+            "&lt;a href=\"x\">'&amp;'"
+        );
+        assert_eq!(
+            escape("<a href=\"x\">'&'", Context::Attr),
+            "<a href=&quot;x&quot;>'&amp;'"
+        );
+    }
+}
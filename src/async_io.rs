@@ -0,0 +1,66 @@
+use std::fmt;
+use std::io;
+
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use super::Result;
+
+/// The output of `render_async!`, which buffers the written nodes until the next flush.
+///
+/// This type is only used in the code generated by `render_async!`.
+pub struct AsyncOutput<W: AsyncWrite + Unpin> {
+    writer: W,
+    buffer: String,
+    error: Option<io::Error>,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncOutput<W> {
+    #[doc(hidden)]
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            buffer: String::new(),
+            error: None,
+        }
+    }
+
+    /// Writes the buffered output into the writer and flushes it.
+    ///
+    /// The I/O error, if any, is stored and reported by `finish`.
+    #[doc(hidden)]
+    pub async fn flush(&mut self) -> Result {
+        let result = async {
+            self.writer.write_all(self.buffer.as_bytes()).await?;
+            self.writer.flush().await
+        }
+        .await;
+        self.buffer.clear();
+        result.map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+
+    /// Flushes the remaining output and converts the result of the rendering.
+    #[doc(hidden)]
+    pub async fn finish(mut self, result: Result) -> io::Result<()> {
+        let result = match result {
+            Ok(()) => self.flush().await,
+            Err(err) => Err(err),
+        };
+        match result {
+            Ok(()) => Ok(()),
+            Err(fmt::Error) => Err(self.error.take().unwrap_or_else(|| {
+                io::Error::other("a ToHtmlNode implementation returned an error")
+            })),
+        }
+    }
+}
+
+impl<W: AsyncWrite + Unpin> fmt::Write for AsyncOutput<W> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> Result {
+        self.buffer.push_str(s);
+        Ok(())
+    }
+}
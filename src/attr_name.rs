@@ -0,0 +1,110 @@
+use std::error::Error;
+use std::fmt;
+
+use super::{is_event_handler, NoSpecial};
+
+/// An attribute name that satisfies the attribute name grammar of the HTML spec.
+///
+/// `&` and `<` are additionally rejected, so a valid name never has special characters.
+/// This type is used to check the names of `dyn` attributes,
+/// and can also be used as the name of a `dyn` attribute directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AttrName<'t>(&'t str);
+
+impl<'t> AttrName<'t> {
+    /// Checks whether `name` is a valid attribute name.
+    pub fn new(name: &'t str) -> Result<Self, InvalidAttrName> {
+        let valid = !name.is_empty()
+            && name.chars().all(|char| {
+                !char.is_control()
+                    && !matches!(
+                        char,
+                        ' ' | '"' | '\'' | '>' | '/' | '=' | '&' | '<' | '\u{fdd0}'..='\u{fdef}'
+                    )
+                    && (u32::from(char) & 0xfffe) != 0xfffe
+            });
+        if valid {
+            Ok(Self(name))
+        } else {
+            Err(InvalidAttrName(name.to_string()))
+        }
+    }
+
+    /// Returns the checked name.
+    #[inline]
+    pub fn as_str(&self) -> &'t str {
+        self.0
+    }
+
+    /// Checks whether the attribute is an event handler, i.e. starts with `on`.
+    #[inline]
+    pub fn is_event_handler(&self) -> bool {
+        is_event_handler(self.0)
+    }
+}
+
+impl<'t> AsRef<str> for AttrName<'t> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
+
+impl<'t> From<AttrName<'t>> for NoSpecial<'t> {
+    #[inline]
+    fn from(name: AttrName<'t>) -> Self {
+        NoSpecial(name.0)
+    }
+}
+
+/// The error returned by `AttrName::new`, containing the rejected name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InvalidAttrName(pub String);
+
+impl fmt::Display for InvalidAttrName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} is not a valid attribute name", self.0)
+    }
+}
+
+impl Error for InvalidAttrName {}
+
+/// What to do when a `dyn` attribute is rejected.
+///
+/// An attribute is rejected if its name is not a valid `AttrName`,
+/// if it is an event handler and the value is not a `TrustedScript`,
+/// or if it is a URL attribute and the value is not a `UrlAttr` or a `TrustedUrl`.
+///
+/// The policy is selected per macro invocation with `#![attr_policy(invalid = ...)]`
+/// at the start of the input.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum InvalidAttrPolicy {
+    /// Omits the attribute.
+    Skip,
+    /// Fails the rendering with `fmt::Error`. This is the default.
+    #[default]
+    Error,
+    /// Panics with a message containing the attribute name.
+    Panic,
+}
+
+/// What to do when an element has multiple attributes with the same name,
+/// at least one of which is a `dyn` attribute.
+///
+/// Names are compared case-insensitively.
+/// Hardcoded attributes always take precedence,
+/// so a `dyn` attribute duplicating a hardcoded one is skipped unless the policy is `Error`.
+/// Duplicates between hardcoded attributes are rejected at compile time.
+///
+/// The policy is selected per macro invocation with `#![attr_policy(duplicate = ...)]`
+/// at the start of the input.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DuplicateAttrPolicy {
+    /// Only writes the last `dyn` attribute with the name.
+    LastWins,
+    /// Only writes the first `dyn` attribute with the name.
+    FirstWins,
+    /// Fails the rendering with `fmt::Error`. This is the default.
+    #[default]
+    Error,
+}
@@ -0,0 +1,88 @@
+use std::fmt;
+
+use super::Result;
+
+/// Escapes `s` for use as text content and writes it into `f`.
+///
+/// Only `&` and `<` are escaped, which is sufficient outside tags.
+/// With the `strict-escaping` feature, `>`, `'` and `"` are escaped as well.
+///
+/// Runs of characters that need no escaping are written as single slices.
+#[inline]
+pub fn escape_text(s: &str, f: &mut dyn fmt::Write) -> Result {
+    if cfg!(feature = "strict-escaping") {
+        return escape(s, f, strict);
+    }
+    escape(s, f, |byte| match byte {
+        b'&' => Some("&amp;"),
+        b'<' => Some("&lt;"),
+        _ => None,
+    })
+}
+
+/// Escapes `s` for use inside a double-quoted attribute value and writes it into `f`.
+///
+/// Only `&` and `"` are escaped, which is sufficient between double quotes.
+/// With the `strict-escaping` feature, `<`, `>` and `'` are escaped as well.
+///
+/// Runs of characters that need no escaping are written as single slices.
+#[inline]
+pub fn escape_attr(s: &str, f: &mut dyn fmt::Write) -> Result {
+    if cfg!(feature = "strict-escaping") {
+        return escape(s, f, strict);
+    }
+    escape(s, f, |byte| match byte {
+        b'&' => Some("&amp;"),
+        b'"' => Some("&quot;"),
+        _ => None,
+    })
+}
+
+fn strict(byte: u8) -> Option<&'static str> {
+    match byte {
+        b'&' => Some("&amp;"),
+        b'<' => Some("&lt;"),
+        b'>' => Some("&gt;"),
+        b'\'' => Some("&apos;"),
+        b'"' => Some("&quot;"),
+        _ => None,
+    }
+}
+
+#[inline]
+pub(crate) fn escape(
+    s: &str,
+    f: &mut dyn fmt::Write,
+    replacement: impl Fn(u8) -> Option<&'static str>,
+) -> Result {
+    let mut start = 0;
+    for (i, byte) in s.bytes().enumerate() {
+        let escape = match replacement(byte) {
+            Some(escape) => escape,
+            None => continue,
+        };
+        // the special characters are all ASCII, so `i` is always on a char boundary
+        if start < i {
+            f.write_str(&s[start..i])?;
+        }
+        f.write_str(escape)?;
+        start = i + 1;
+    }
+    if start < s.len() {
+        f.write_str(&s[start..])?;
+    }
+    Ok(())
+}
+
+/// A `fmt::Write` adapter that escapes everything written into it.
+pub(crate) struct EscapeWriter<'a> {
+    pub(crate) inner: &'a mut dyn fmt::Write,
+    pub(crate) escape: fn(&str, &mut dyn fmt::Write) -> Result,
+}
+
+impl<'a> fmt::Write for EscapeWriter<'a> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> Result {
+        (self.escape)(s, self.inner)
+    }
+}
@@ -1,6 +1,10 @@
 use std::fmt;
 
-use super::{Result, ToHtmlAttr};
+use super::trust::{Token, Trust};
+use super::{
+    escape_attr, is_url_attr, AttrName, DuplicateAttrPolicy, InvalidAttrPolicy, NoSpecial, Result,
+    ToHtmlAttr, ToWholeHtmlAttr,
+};
 
 /// Concatenates hardcoded and dynamic classes.
 ///
@@ -14,12 +18,76 @@ impl<A> ToHtmlAttr for ClassConcat<A>
 where
     A: AsRef<str>,
 {
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result {
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
         write!(f, "{}", self.1)?;
         let str = self.0.as_ref();
-        if str.len() > 0 {
-            write!(f, " {}", str)?;
+        if !str.is_empty() {
+            f.write_str(" ")?;
+            escape_attr(str, f)?;
         }
         Ok(())
     }
 }
+
+/// Checks the name of a `dyn` attribute before it is written.
+///
+/// The name must be a valid `AttrName`.
+/// It may only be an event handler if the value is a `TrustedScript`,
+/// and it may only be a URL attribute if the value is a `UrlAttr` or a `TrustedUrl`.
+/// Rejected attributes are handled according to `policy`,
+/// returning `Ok(None)` if the attribute should be skipped.
+/// Unlike `NoSpecial::debug_checked`, this check is also performed in release builds.
+pub fn dyn_attr_name<'t>(
+    name: &'t str,
+    value: &dyn ToWholeHtmlAttr,
+    policy: InvalidAttrPolicy,
+) -> Result<Option<NoSpecial<'t>>> {
+    let trust = value.trust(Token(()));
+    let error = match AttrName::new(name) {
+        Ok(name) if name.is_event_handler() && trust != Trust::Script => format!(
+            "The event handler attribute {:?} requires a TrustedScript value",
+            name.as_str()
+        ),
+        Ok(name) if is_url_attr(name.as_str()) && trust != Trust::Url => format!(
+            "The URL attribute {:?} requires a UrlAttr or TrustedUrl value",
+            name.as_str()
+        ),
+        Ok(name) => return Ok(Some(name.into())),
+        Err(err) => err.to_string(),
+    };
+    match policy {
+        InvalidAttrPolicy::Skip => Ok(None),
+        InvalidAttrPolicy::Error => Err(fmt::Error),
+        InvalidAttrPolicy::Panic => panic!("{}", error),
+    }
+}
+
+/// Checks whether the `dyn` attribute at `index` should be written,
+/// according to `policy`.
+///
+/// `names` contains the names of all `dyn` attributes of the element in source order,
+/// and `static_names` contains the names of the hardcoded attributes,
+/// which always take precedence over `dyn` attributes.
+pub fn keep_dyn_attr(
+    names: &[&str],
+    index: usize,
+    static_names: &[&str],
+    policy: DuplicateAttrPolicy,
+) -> Result<bool> {
+    let name = names[index];
+    let same = |other: &&str| other.eq_ignore_ascii_case(name);
+    if static_names.iter().any(same) {
+        return match policy {
+            DuplicateAttrPolicy::Error => Err(fmt::Error),
+            _ => Ok(false),
+        };
+    }
+    let earlier = names[..index].iter().any(same);
+    let later = names[index + 1..].iter().any(same);
+    match policy {
+        DuplicateAttrPolicy::LastWins => Ok(!later),
+        DuplicateAttrPolicy::FirstWins => Ok(!earlier),
+        DuplicateAttrPolicy::Error if earlier || later => Err(fmt::Error),
+        DuplicateAttrPolicy::Error => Ok(true),
+    }
+}
@@ -1,11 +1,40 @@
 use std::fmt;
+use std::io;
 
 pub type Result<T = (), E = fmt::Error> = std::result::Result<T, E>;
 
 /// A Node is either an element or a text string
+///
+/// Nodes are written into a `fmt::Write`,
+/// which may be a `String`, a `fmt::Formatter` or an adapter over an `io::Write`
+/// (see `render_to_io`).
 pub trait ToHtmlNode {
     /// Writes the content of the node.
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result;
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result;
+
+    /// Returns an estimate of the number of bytes written by `fmt`.
+    ///
+    /// This is only used for preallocating buffers, so it does not need to be exact.
+    #[inline]
+    fn size_hint(&self) -> usize {
+        0
+    }
+}
+
+/// A reusable fragment that can be invoked in `html!`.
+///
+/// `@Card(title = t) { children }` constructs the props struct `Card { title: t }` and calls
+/// `Component::fmt` on it, passing the nodes in the braces as `children`.
+/// The parentheses and braces are both optional.
+///
+/// A component may receive multiple fragments by name through `@slot` blocks in the children:
+/// `@Layout(title = t) { @slot sidebar { ... } main { ... } }` passes the contents of the
+/// `sidebar` block as the `sidebar` field of `Layout`, leaving the rest as `children`.
+/// Slot fields are typically generic over `ToHtmlNode`.
+/// As with any other field, omitting a slot that the struct declares is a compile error.
+pub trait Component {
+    /// Writes the component, with `children` being the nodes passed to the invocation.
+    fn fmt(&self, children: &dyn ToHtmlNode, f: &mut dyn fmt::Write) -> Result;
 }
 
 /// Writes an attribute, where the name is given and the value is `self`.
@@ -17,7 +46,16 @@ pub trait ToHtmlNode {
 pub trait ToWholeHtmlAttr {
     /// Writes the whole attribute, **including the leading space before the attribute name**, to the
     /// output.
-    fn fmt(&self, name: NoSpecial<'_>, f: &mut fmt::Formatter) -> Result;
+    fn fmt(&self, name: NoSpecial<'_>, f: &mut dyn fmt::Write) -> Result;
+
+    /// Returns the kind of sensitive `dyn` attribute that the value may be used for.
+    ///
+    /// This is only overridden by the implementors of the sealed trait `TrustedAttr`.
+    #[doc(hidden)]
+    #[inline]
+    fn trust(&self, _: trust::Token) -> trust::Trust {
+        trust::Trust::None
+    }
 }
 
 /// Writes an attribute value.
@@ -29,12 +67,12 @@ pub trait ToHtmlAttr {
     ///
     /// It is the responsibility of the implementor to make sure that no `"` characters are written
     /// during this function call.
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result;
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result;
 }
 
 impl<T: ToHtmlAttr + ?Sized> ToWholeHtmlAttr for T {
     #[inline]
-    fn fmt(&self, name: NoSpecial<'_>, f: &mut fmt::Formatter) -> Result {
+    fn fmt(&self, name: NoSpecial<'_>, f: &mut dyn fmt::Write) -> Result {
         write!(f, " {}=\"", name.0)?;
         ToHtmlAttr::fmt(self, f)?;
         write!(f, "\"")?;
@@ -65,85 +103,327 @@ impl<'t> NoSpecial<'t> {
 }
 
 fn has_special_chars(s: &str) -> bool {
-    s.contains(|c| match c {
-        '&' | '<' | '>' | '\'' | '"' => true,
-        _ => false,
-    })
+    s.contains(['&', '<', '>', '\'', '"'])
 }
 
 impl<'t> ToHtmlNode for NoSpecial<'t> {
     #[inline]
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result {
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
         write!(f, "{}", self.0)
     }
+
+    #[inline]
+    fn size_hint(&self) -> usize {
+        self.0.len()
+    }
 }
 
 impl<'t> ToHtmlAttr for NoSpecial<'t> {
     #[inline]
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result {
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
         write!(f, "{}", self.0)
     }
 }
 
-struct Escaped<'t>(&'t str);
+/// Writes the wrapped string verbatim in text mode, without any escaping.
+///
+/// This is intended for trusted, pre-rendered HTML, such as the output of a markdown renderer or
+/// a cached fragment.
+/// Never wrap user input with this struct.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Raw<T: AsRef<str>>(pub T);
 
-impl<'t> fmt::Display for Escaped<'t> {
+/// An owned string of trusted, pre-rendered HTML.
+pub type PreEscaped = Raw<String>;
+
+impl<T: AsRef<str>> ToHtmlNode for Raw<T> {
     #[inline]
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result {
-        for char in self.0.chars() {
-            let escape = match char {
-                '&' => "&amp;",
-                '<' => "&lt;",
-                '>' => "&gt;",
-                '\'' => "&apos;",
-                '"' => "&quot;",
-                _ => {
-                    write!(f, "{}", char)?;
-                    continue;
-                }
-            };
-            write!(f, "{}", escape)?;
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        f.write_str(self.0.as_ref())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> usize {
+        self.0.as_ref().len()
+    }
+}
+
+/// Writes each item of an iterator as a node.
+///
+/// The iterator is cloned every time the node is written, so this adapter is most suitable for
+/// lazy iterator chains over borrowed data, e.g. `Iter(items.iter().map(|item| ...))`.
+///
+/// One-shot iterators that are not `Clone`, such as `vec.drain(..)` or chains whose closures
+/// capture non-`Clone` values, are not supported, since a node may be written more than once.
+/// Collect them into a `Vec` first, which is a node itself.
+#[derive(Debug, Clone, Copy)]
+pub struct Iter<I>(pub I)
+where
+    I: IntoIterator + Clone,
+    I::Item: ToHtmlNode;
+
+impl<I> ToHtmlNode for Iter<I>
+where
+    I: IntoIterator + Clone,
+    I::Item: ToHtmlNode,
+{
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        for node in self.0.clone() {
+            ToHtmlNode::fmt(&node, f)?;
         }
         Ok(())
     }
 }
 
+/// Writes the `Display` output of the wrapped value, escaped.
+///
+/// The output is escaped on the fly as it is being formatted, so no intermediate `String` is
+/// allocated.
+/// This type can be used both as a node and as an attribute value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Text<D: fmt::Display>(pub D);
+
+impl<D: fmt::Display> ToHtmlNode for Text<D> {
+    #[inline]
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        let mut writer = EscapeWriter {
+            inner: f,
+            escape: escape_text,
+        };
+        fmt::Write::write_fmt(&mut writer, format_args!("{}", self.0))
+    }
+}
+
+impl<D: fmt::Display> ToHtmlAttr for Text<D> {
+    #[inline]
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        let mut writer = EscapeWriter {
+            inner: f,
+            escape: escape_attr,
+        };
+        fmt::Write::write_fmt(&mut writer, format_args!("{}", self.0))
+    }
+}
+
+mod escape;
+use escape::EscapeWriter;
+
+mod url;
+pub use url::{is_safe_url, ToUrlAttr, TrustedUrl, UrlAttr, INVALID_URL};
+
+pub use minihtml_attrs::{is_event_handler, is_url_attr, URL_ATTRS};
+
+mod attr_name;
+pub use attr_name::{AttrName, DuplicateAttrPolicy, InvalidAttrName, InvalidAttrPolicy};
+
+mod script;
+pub use script::{ToScriptAttr, TrustedScript};
+
+mod trust;
+pub use trust::TrustedAttr;
+
+mod sanitize;
+pub use sanitize::{Sanitized, Sanitizer};
+
+mod raw_text;
+pub use escape::{escape_attr, escape_text};
+#[cfg(feature = "json")]
+pub use raw_text::ScriptJson;
+pub use raw_text::{Css, ToRawTextNode};
+
 mod primitives;
 
+/// Generates a `ToHtmlNode` from the given nodes.
+///
+/// # Attribute order
+/// Attributes are written in a deterministic order that does not change between compilations:
+/// the `#id` shorthand comes first, followed by the `.class` shorthand,
+/// followed by the parenthesized attributes (including `dyn` attributes) in source order.
+/// If `class = ...` is also passed in the parentheses,
+/// it is merged into the `.class` shorthand at the shorthand position.
+/// If an element has `dyn` attributes, all attribute names and values of the element are
+/// evaluated in source order before any of them is written.
+///
+/// # Attribute policies
+/// `dyn` attributes are checked when they are written
+/// (see `InvalidAttrPolicy` and `DuplicateAttrPolicy`).
+/// The policies can be selected with an inner attribute at the start of the input:
+///
+/// ```
+/// # use minihtml::try_html_string;
+/// let name = "a b";
+/// let ret = try_html_string! {
+///     #![attr_policy(invalid = Skip, duplicate = LastWins)]
+///     div(dyn name = 1, title = "t", dyn "title" = 2) {}
+/// };
+/// assert_eq!(ret.unwrap(), r#"<div title="t"></div>"#);
+/// ```
 #[proc_macro_hack::proc_macro_hack]
 pub use minihtml_codegen::html;
 
+/// Generates a `TryHtml` from the given nodes, which can be rendered with `render`.
+///
+/// The syntax is identical to `html!`,
+/// except that `?` can be used in node and attribute expressions to return an error of type `E`,
+/// where `E` is the error type of the `render` call and implements `From<fmt::Error>`.
+///
+/// Errors other than `fmt::Error` cannot be propagated from component children and slots,
+/// since those are passed to components as `ToHtmlNode`.
+#[proc_macro_hack::proc_macro_hack]
+pub use minihtml_codegen::try_html;
+
+/// The output of `html!`.
+///
+/// The first field writes the nodes.
+/// The second field computes the size hint from the static literals and the dynamic nodes.
 #[doc(hidden)]
-pub struct HtmlString<T: ToHtmlNode>(pub T);
+pub struct Html<F, H>(pub F, pub H)
+where
+    F: Fn(&mut dyn fmt::Write) -> fmt::Result,
+    H: Fn() -> usize;
 
-impl<T: ToHtmlNode> fmt::Display for HtmlString<T> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result {
-        ToHtmlNode::fmt(&self.0, f)
+impl<F, H> ToHtmlNode for Html<F, H>
+where
+    F: Fn(&mut dyn fmt::Write) -> fmt::Result,
+    H: Fn() -> usize,
+{
+    fn fmt(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        (self.0)(f)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> usize {
+        (self.1)()
     }
 }
 
+/// Renders nodes into a `tokio::io::AsyncWrite`, flushing the output before awaiting futures.
+///
+/// The first argument is the writer, which must implement `AsyncWrite + Unpin`
+/// (e.g. `&mut TcpStream`), followed by a comma and the nodes in the same syntax as `html!`.
+/// The macro evaluates to a future that resolves to `std::io::Result<()>`.
+///
+/// `+` nodes containing `.await`, e.g. `+load().await` or `+(load().await?)`, are awaited in
+/// document order.
+/// Everything rendered before such a node is flushed to the writer before it is evaluated,
+/// so that e.g. the `<head>` of a page can be sent while the body is still being loaded.
+/// `.await` is not supported inside component children and slots.
+///
+/// This macro is only available with the `tokio` feature.
+#[cfg(feature = "tokio")]
+#[proc_macro_hack::proc_macro_hack]
+pub use minihtml_codegen::render_async;
+
+#[cfg(feature = "tokio")]
+mod async_io;
+#[cfg(feature = "tokio")]
+pub use async_io::AsyncOutput;
+
+/// The output of `try_html!`, which can return a user-defined error type.
 #[doc(hidden)]
-pub struct Html<F>(pub F)
+pub struct TryHtml<F, H>(pub F, pub H);
+
+/// Renders the output of `try_html!` into a string, propagating the error returned by it.
+pub fn render<F, H, E>(html: &TryHtml<F, H>) -> Result<String, E>
 where
-    F: Fn(&mut ::std::fmt::Formatter) -> fmt::Result;
+    F: Fn(&mut dyn fmt::Write) -> Result<(), E>,
+    H: Fn() -> usize,
+    E: From<fmt::Error>,
+{
+    let mut output = String::with_capacity((html.1)());
+    (html.0)(&mut output)?;
+    Ok(output)
+}
+
+/// Renders a node into a string, returning the error if any node fails to write.
+///
+/// The capacity of the string is reserved from `ToHtmlNode::size_hint`.
+pub fn render_to_string<T: ToHtmlNode + ?Sized>(node: &T) -> Result<String> {
+    let mut output = String::with_capacity(node.size_hint());
+    ToHtmlNode::fmt(node, &mut output)?;
+    Ok(output)
+}
 
-impl<F> ToHtmlNode for Html<F>
+/// Renders a node into an `io::Write`, returning the first I/O error encountered.
+///
+/// The output is written into `writer` as it is generated without being collected into a string
+/// first, so the memory usage does not grow with the size of the output.
+/// Since nodes usually perform many small writes, `writer` should be buffered,
+/// e.g. with `std::io::BufWriter`.
+pub fn render_to_io<T, W>(node: &T, writer: &mut W) -> io::Result<()>
 where
-    F: Fn(&mut ::std::fmt::Formatter) -> fmt::Result,
+    T: ToHtmlNode + ?Sized,
+    W: io::Write + ?Sized,
 {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        (self.0)(f)
+    let mut adapter = IoWriter {
+        writer,
+        error: None,
+    };
+    match ToHtmlNode::fmt(node, &mut adapter) {
+        Ok(()) => Ok(()),
+        Err(fmt::Error) => Err(adapter
+            .error
+            .unwrap_or_else(|| io::Error::other("a ToHtmlNode implementation returned an error"))),
+    }
+}
+
+/// A `fmt::Write` adapter that writes into an `io::Write`, storing the I/O error.
+struct IoWriter<'w, W: io::Write + ?Sized> {
+    writer: &'w mut W,
+    error: Option<io::Error>,
+}
+
+impl<'w, W: io::Write + ?Sized> fmt::Write for IoWriter<'w, W> {
+    fn write_str(&mut self, s: &str) -> Result {
+        self.writer.write_all(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
     }
 }
 
+/// Renders a node into a string, panicking if any node fails to write.
+///
+/// Use `render_to_string` if the nodes may return errors.
+pub fn render_to_string_or_panic<T: ToHtmlNode + ?Sized>(node: &T) -> String {
+    render_to_string(node).expect("a ToHtmlNode implementation returned an error")
+}
+
 /// Returns the string containing the HTML strings.
 ///
-/// The return type is `Result<std::string::String, std::fmt::Error>`.
+/// The return type is `Result<std::string::String, std::fmt::Error>`,
+/// which is an error if any node fails to write.
+#[macro_export]
+macro_rules! try_html_string {
+    ($($tt:tt)*) => {{
+        let node = $crate::html!($($tt)*);
+        $crate::render_to_string(&node)
+    }}
+}
+
+/// Returns the string containing the HTML strings.
+///
+/// The return type is `std::string::String`.
+///
+/// # Panics
+/// Panics if any node fails to write, which only happens if a `ToHtmlNode` implementation
+/// returns an error.
+/// Use `try_html_string!` to handle the error instead.
+#[macro_export]
+macro_rules! html_string_or_panic {
+    ($($tt:tt)*) => {{
+        let node = $crate::html!($($tt)*);
+        $crate::render_to_string_or_panic(&node)
+    }}
+}
+
+/// Returns the string containing the HTML strings, panicking if any node fails to write.
+#[deprecated(note = "use `try_html_string!`, or `html_string_or_panic!` to keep panicking")]
 #[macro_export]
 macro_rules! html_string {
     ($($tt:tt)*) => {{
         let node = $crate::html!($($tt)*);
-        format!("{}", $crate::HtmlString(node))
+        $crate::render_to_string_or_panic(&node)
     }}
 }
 
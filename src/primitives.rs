@@ -1,38 +1,76 @@
+use std::borrow::Cow;
 use std::fmt;
+use std::rc::Rc;
+use std::sync::Arc;
 
-use super::{Escaped, NoSpecial, Result, ToHtmlAttr, ToHtmlNode, ToWholeHtmlAttr};
+use super::{escape_attr, escape_text, NoSpecial, Result, ToHtmlAttr, ToHtmlNode, ToWholeHtmlAttr};
 
 impl<T: ToHtmlNode + ?Sized> ToHtmlNode for &T {
     #[inline]
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result {
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
         ToHtmlNode::fmt(&**self, f)
     }
+
+    #[inline]
+    fn size_hint(&self) -> usize {
+        ToHtmlNode::size_hint(&**self)
+    }
 }
 
 impl<T: ToHtmlAttr + ?Sized> ToHtmlAttr for &T {
     #[inline]
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result {
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
         ToHtmlAttr::fmt(&**self, f)
     }
 }
 
+macro_rules! impl_deref {
+    ($($ty:ty),*) => {$(
+        impl<T: ToHtmlNode + ?Sized> ToHtmlNode for $ty {
+            #[inline]
+            fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+                ToHtmlNode::fmt(&**self, f)
+            }
+
+            #[inline]
+            fn size_hint(&self) -> usize {
+                ToHtmlNode::size_hint(&**self)
+            }
+        }
+
+        impl<T: ToHtmlAttr + ?Sized> ToHtmlAttr for $ty {
+            #[inline]
+            fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+                ToHtmlAttr::fmt(&**self, f)
+            }
+        }
+    )*};
+}
+
+impl_deref!(Box<T>, Rc<T>, Arc<T>);
+
 impl ToHtmlNode for str {
     #[inline]
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result {
-        write!(f, "{}", Escaped(self.as_ref()))
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        escape_text(self, f)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> usize {
+        self.len()
     }
 }
 
 impl ToHtmlAttr for str {
     #[inline]
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result {
-        write!(f, "{}", Escaped(self.as_ref()))
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        escape_attr(self, f)
     }
 }
 
 impl ToWholeHtmlAttr for bool {
     #[inline]
-    fn fmt(&self, name: NoSpecial<'_>, f: &mut fmt::Formatter) -> Result {
+    fn fmt(&self, name: NoSpecial<'_>, f: &mut dyn fmt::Write) -> Result {
         if *self {
             write!(f, " {}", name.0)?;
         }
@@ -42,7 +80,7 @@ impl ToWholeHtmlAttr for bool {
 
 impl<T: ToHtmlAttr> ToWholeHtmlAttr for Option<T> {
     #[inline]
-    fn fmt(&self, name: NoSpecial<'_>, f: &mut fmt::Formatter) -> Result {
+    fn fmt(&self, name: NoSpecial<'_>, f: &mut dyn fmt::Write) -> Result {
         if let Some(value) = self {
             write!(f, " {}=\"", name.0)?;
             ToHtmlAttr::fmt(value, f)?;
@@ -51,3 +89,170 @@ impl<T: ToHtmlAttr> ToWholeHtmlAttr for Option<T> {
         Ok(())
     }
 }
+
+impl ToHtmlNode for String {
+    #[inline]
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        ToHtmlNode::fmt(self.as_str(), f)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
+}
+
+impl ToHtmlAttr for String {
+    #[inline]
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        ToHtmlAttr::fmt(self.as_str(), f)
+    }
+}
+
+impl<'t> ToHtmlNode for Cow<'t, str> {
+    #[inline]
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        ToHtmlNode::fmt(&**self, f)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<'t> ToHtmlAttr for Cow<'t, str> {
+    #[inline]
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        ToHtmlAttr::fmt(&**self, f)
+    }
+}
+
+impl ToHtmlNode for char {
+    #[inline]
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        ToHtmlNode::fmt(&*self.encode_utf8(&mut [0; 4]), f)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> usize {
+        self.len_utf8()
+    }
+}
+
+impl ToHtmlAttr for char {
+    #[inline]
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        ToHtmlAttr::fmt(&*self.encode_utf8(&mut [0; 4]), f)
+    }
+}
+
+/// The `Display` output of these types never contains special characters,
+/// so they are written directly without escaping.
+macro_rules! impl_no_special_display {
+    ($($ty:ty),*) => {$(
+        impl ToHtmlNode for $ty {
+            #[inline]
+            fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+                write!(f, "{}", self)
+            }
+        }
+
+        impl ToHtmlAttr for $ty {
+            #[inline]
+            fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+                write!(f, "{}", self)
+            }
+        }
+    )*};
+}
+
+impl_no_special_display!(u8, u16, u32, u64, u128, usize);
+impl_no_special_display!(i8, i16, i32, i64, i128, isize);
+impl_no_special_display!(f32, f64);
+
+impl<T: ToHtmlNode> ToHtmlNode for Option<T> {
+    #[inline]
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        match self {
+            Some(node) => ToHtmlNode::fmt(node, f),
+            None => Ok(()),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> usize {
+        self.as_ref().map_or(0, ToHtmlNode::size_hint)
+    }
+}
+
+impl<T: ToHtmlNode> ToHtmlNode for [T] {
+    #[inline]
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        for node in self {
+            ToHtmlNode::fmt(node, f)?;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> usize {
+        self.iter().map(ToHtmlNode::size_hint).sum()
+    }
+}
+
+impl<T: ToHtmlNode, const N: usize> ToHtmlNode for [T; N] {
+    #[inline]
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        ToHtmlNode::fmt(&self[..], f)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> usize {
+        ToHtmlNode::size_hint(&self[..])
+    }
+}
+
+impl<T: ToHtmlNode> ToHtmlNode for Vec<T> {
+    #[inline]
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        ToHtmlNode::fmt(self.as_slice(), f)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> usize {
+        ToHtmlNode::size_hint(self.as_slice())
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($name:ident)*) => {
+        impl<$($name: ToHtmlNode),*> ToHtmlNode for ($($name,)*) {
+            #[inline]
+            #[allow(non_snake_case)]
+            fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+                let ($($name,)*) = self;
+                $(
+                    ToHtmlNode::fmt($name, f)?;
+                )*
+                Ok(())
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            fn size_hint(&self) -> usize {
+                let ($($name,)*) = self;
+                0 $(+ ToHtmlNode::size_hint($name))*
+            }
+        }
+    };
+}
+
+impl_tuple!(A);
+impl_tuple!(A B);
+impl_tuple!(A B C);
+impl_tuple!(A B C D);
+impl_tuple!(A B C D E);
+impl_tuple!(A B C D E F);
+impl_tuple!(A B C D E F G);
+impl_tuple!(A B C D E F G H);
@@ -0,0 +1,104 @@
+use std::fmt;
+
+use super::{Raw, Result};
+
+/// Allows a type to be used as the content of a raw text element, i.e. `script` or `style`.
+///
+/// Browsers do not unescape the content of raw text elements, so `ToHtmlNode` values cannot be
+/// written there.
+/// Implementations must never write `</script`, `</style` (in any case) or `<!--`, which could
+/// close the element early.
+pub trait ToRawTextNode {
+    /// Writes the content into `f`.
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result;
+}
+
+impl<T: ToRawTextNode + ?Sized> ToRawTextNode for &T {
+    #[inline]
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        ToRawTextNode::fmt(&**self, f)
+    }
+}
+
+/// Writes the wrapped string verbatim.
+///
+/// Returns an error if the string contains `</script`, `</style` or `<!--`.
+impl<T: AsRef<str>> ToRawTextNode for Raw<T> {
+    #[inline]
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        let value = self.0.as_ref();
+        if value
+            .match_indices('<')
+            .any(|(pos, _)| closes_raw_text(&value[pos..]))
+        {
+            return Err(fmt::Error);
+        }
+        f.write_str(value)
+    }
+}
+
+/// Writes a stylesheet into a `style` element.
+///
+/// A `<` that starts `</style` or `<!--` is written as the CSS escape `\3c `, so the stylesheet
+/// cannot close the element. Other `<` characters, e.g. in `@media (width < 600px)`, are written
+/// as is.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Css<T: AsRef<str>>(pub T);
+
+impl<T: AsRef<str>> ToRawTextNode for Css<T> {
+    #[inline]
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        let css = self.0.as_ref();
+        let mut start = 0;
+        for (pos, _) in css.match_indices('<') {
+            if closes_raw_text(&css[pos..]) {
+                f.write_str(&css[start..pos])?;
+                f.write_str("\\3c ")?;
+                start = pos + 1;
+            }
+        }
+        f.write_str(&css[start..])
+    }
+}
+
+/// Checks whether `s`, which starts with `<`, could end a raw text element or start a comment.
+fn closes_raw_text(s: &str) -> bool {
+    s.starts_with("<!--")
+        || ["</script", "</style"].iter().any(|end| {
+            s.get(..end.len())
+                .is_some_and(|start| start.eq_ignore_ascii_case(end))
+        })
+}
+
+/// Serializes the wrapped value as JSON into a `script` element.
+///
+/// `<`, `>` and `&` are written as unicode escapes, so the output is a valid JavaScript
+/// expression that cannot close the element.
+///
+/// ```
+/// # use minihtml::{html_string_or_panic, ScriptJson};
+/// let data = vec!["</script>"];
+/// let ret = html_string_or_panic! {
+///     script { +"var data = "; +ScriptJson(&data) }
+/// };
+/// assert_eq!(
+///     ret,
+///     r#"<script>var data = ["\u003c/script\u003e"]</script>"#
+/// );
+/// ```
+#[cfg(feature = "json")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ScriptJson<T: serde::Serialize>(pub T);
+
+#[cfg(feature = "json")]
+impl<T: serde::Serialize> ToRawTextNode for ScriptJson<T> {
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        let json = serde_json::to_string(&self.0).map_err(|_| fmt::Error)?;
+        super::escape::escape(&json, f, |byte| match byte {
+            b'<' => Some("\\u003c"),
+            b'>' => Some("\\u003e"),
+            b'&' => Some("\\u0026"),
+            _ => None,
+        })
+    }
+}
@@ -0,0 +1,382 @@
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use super::{
+    escape_attr, escape_text, is_event_handler, is_url_attr, url, AttrName, Result, ToHtmlNode,
+};
+
+/// Elements whose content is not HTML.
+///
+/// They are always removed together with their content, even if they are allowed.
+const RAW_TEXT: &[&str] = &[
+    "script", "style", "iframe", "noembed", "noframes", "noscript", "textarea", "title", "xmp",
+];
+
+/// Elements that never have children.
+const VOID: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// An allowlist of elements and attributes for rendering untrusted HTML.
+///
+/// Elements that are not allowed are removed, but their content is kept.
+/// Attributes that are not allowed are removed.
+/// Event handler attributes are always removed,
+/// and URL attributes (see `URL_ATTRS`) are checked by `is_safe_url`.
+///
+/// ```
+/// # use minihtml::{html_string_or_panic, Sanitizer};
+/// let sanitizer = Sanitizer::basic();
+/// let comment = r#"<p onclick="steal()">Hi <a href="javascript:steal()">there</a><script>steal()</script>"#;
+/// let ret = html_string_or_panic! {
+///     div.comment { +sanitizer.sanitize(comment) }
+/// };
+/// assert_eq!(
+///     ret,
+///     r#"<div class="comment"><p>Hi <a href="about:invalid">there</a></p></div>"#
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Sanitizer {
+    elements: HashSet<String>,
+    attributes: HashMap<String, HashSet<String>>,
+    global_attributes: HashSet<String>,
+}
+
+impl Sanitizer {
+    /// Creates a sanitizer that does not allow any elements, i.e. only keeps the text.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a sanitizer that allows basic formatting, lists and links.
+    pub fn basic() -> Self {
+        Self::new()
+            .allow_elements(&[
+                "a",
+                "b",
+                "blockquote",
+                "br",
+                "code",
+                "em",
+                "i",
+                "li",
+                "ol",
+                "p",
+                "pre",
+                "strong",
+                "ul",
+            ])
+            .allow_attributes("a", &["href", "title"])
+    }
+
+    /// Allows the elements.
+    pub fn allow_elements(mut self, names: &[&str]) -> Self {
+        self.elements
+            .extend(names.iter().map(|name| name.to_ascii_lowercase()));
+        self
+    }
+
+    /// Allows the attributes on the element.
+    ///
+    /// # Panics
+    /// Panics if any name is not a valid `AttrName`.
+    pub fn allow_attributes(mut self, element: &str, names: &[&str]) -> Self {
+        self.attributes
+            .entry(element.to_ascii_lowercase())
+            .or_default()
+            .extend(names.iter().map(|name| checked_attr_name(name)));
+        self
+    }
+
+    /// Allows the attributes on all allowed elements.
+    ///
+    /// # Panics
+    /// Panics if any name is not a valid `AttrName`.
+    pub fn allow_global_attributes(mut self, names: &[&str]) -> Self {
+        self.global_attributes
+            .extend(names.iter().map(|name| checked_attr_name(name)));
+        self
+    }
+
+    /// Returns a node that writes the sanitized `input`.
+    pub fn sanitize<'t>(&'t self, input: &'t str) -> Sanitized<'t> {
+        Sanitized {
+            sanitizer: self,
+            input,
+        }
+    }
+
+    fn allows_attribute(&self, element: &str, name: &str) -> bool {
+        !is_event_handler(name)
+            && (self.global_attributes.contains(name)
+                || self
+                    .attributes
+                    .get(element)
+                    .is_some_and(|names| names.contains(name)))
+    }
+}
+
+fn checked_attr_name(name: &str) -> String {
+    match AttrName::new(name) {
+        Ok(name) => name.as_str().to_ascii_lowercase(),
+        Err(err) => panic!("{}", err),
+    }
+}
+
+/// Untrusted HTML that is parsed and written again with only the allowed elements and attributes.
+///
+/// Created by `Sanitizer::sanitize`.
+/// The output is always well-formed: unclosed elements are closed,
+/// and end tags without a matching start tag are removed.
+#[derive(Debug, Clone, Copy)]
+pub struct Sanitized<'t> {
+    sanitizer: &'t Sanitizer,
+    input: &'t str,
+}
+
+impl<'t> ToHtmlNode for Sanitized<'t> {
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        let mut open = vec![];
+        let mut rest = self.input;
+        while let Some(pos) = rest.find('<') {
+            escape_text(&decode(&rest[..pos]), f)?;
+            rest = self.write_markup(&rest[pos + 1..], &mut open, f)?;
+        }
+        escape_text(&decode(rest), f)?;
+        for name in open.iter().rev() {
+            write!(f, "</{}>", name)?;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> usize {
+        self.input.len()
+    }
+}
+
+impl<'t> Sanitized<'t> {
+    /// Writes the markup after a `<` and returns the rest of the input.
+    fn write_markup(
+        &self,
+        input: &'t str,
+        open: &mut Vec<String>,
+        f: &mut dyn fmt::Write,
+    ) -> Result<&'t str> {
+        if let Some(comment) = input.strip_prefix("!--") {
+            // `<!-->` and `<!--->` are closed empty comments
+            if let Some(rest) = comment.strip_prefix('>') {
+                return Ok(rest);
+            }
+            if let Some(rest) = comment.strip_prefix("->") {
+                return Ok(rest);
+            }
+            return Ok(comment.find("-->").map_or("", |end| &comment[end + 3..]));
+        }
+        if input.starts_with('!') || input.starts_with('?') {
+            return Ok(skip_past(input, '>'));
+        }
+
+        if let Some(end_tag) = input.strip_prefix('/') {
+            let (name, _) = split_tag_name(end_tag);
+            let name = name.to_ascii_lowercase();
+            if let Some(index) = open.iter().rposition(|open| *open == name) {
+                for name in open.drain(index..).rev() {
+                    write!(f, "</{}>", name)?;
+                }
+            }
+            return Ok(skip_past(end_tag, '>'));
+        }
+
+        if !input.starts_with(|char: char| char.is_ascii_alphabetic()) {
+            escape_text("<", f)?;
+            return Ok(input);
+        }
+
+        let (name, rest) = split_tag_name(input);
+        let name = name.to_ascii_lowercase();
+        let (attrs, rest) = match parse_attrs(rest) {
+            Some(parsed) => parsed,
+            // an unterminated tag is ignored by browsers
+            None => return Ok(""),
+        };
+
+        if RAW_TEXT.contains(&name.as_str()) {
+            return Ok(skip_raw_text(rest, &name));
+        }
+        if !self.sanitizer.elements.contains(&name) {
+            return Ok(rest);
+        }
+
+        write!(f, "<{}", name)?;
+        let mut written = vec![];
+        for (attr, value) in attrs {
+            let attr = attr.to_ascii_lowercase();
+            // browsers use the first attribute if there are duplicates
+            if !self.sanitizer.allows_attribute(&name, &attr) || written.contains(&attr) {
+                continue;
+            }
+            write!(f, " {}", attr)?;
+            if let Some(value) = value {
+                let value = decode(value);
+                f.write_str("=\"")?;
+                if is_url_attr(&attr) {
+                    url::ToUrlAttr::fmt(&*value, f)?;
+                } else {
+                    escape_attr(&value, f)?;
+                }
+                f.write_str("\"")?;
+            }
+            written.push(attr);
+        }
+        if VOID.contains(&name.as_str()) {
+            f.write_str("/>")?;
+        } else {
+            f.write_str(">")?;
+            open.push(name);
+        }
+        Ok(rest)
+    }
+}
+
+fn is_tag_space(char: char) -> bool {
+    char.is_ascii_whitespace() || char == '/'
+}
+
+fn skip_past(input: &str, char: char) -> &str {
+    input.find(char).map_or("", |end| &input[end + 1..])
+}
+
+fn split_tag_name(input: &str) -> (&str, &str) {
+    let end = input
+        .find(|char: char| is_tag_space(char) || char == '>')
+        .unwrap_or(input.len());
+    input.split_at(end)
+}
+
+/// The names and values of the attributes in a start tag, before decoding.
+type Attrs<'t> = Vec<(&'t str, Option<&'t str>)>;
+
+/// Parses the attributes of a start tag until `>`.
+///
+/// Returns `None` if the tag is not terminated.
+fn parse_attrs(mut input: &str) -> Option<(Attrs<'_>, &str)> {
+    let mut attrs = vec![];
+    loop {
+        input = input.trim_start_matches(is_tag_space);
+        if let Some(rest) = input.strip_prefix('>') {
+            return Some((attrs, rest));
+        }
+        let first = input.chars().next()?;
+        // the first character may be `=`
+        let end = input[first.len_utf8()..]
+            .find(|char: char| is_tag_space(char) || char == '>' || char == '=')
+            .map_or(input.len(), |end| end + first.len_utf8());
+        let name = &input[..end];
+        input = input[end..].trim_start_matches(|char: char| char.is_ascii_whitespace());
+
+        let value = match input.strip_prefix('=') {
+            Some(rest) => {
+                let rest = rest.trim_start_matches(|char: char| char.is_ascii_whitespace());
+                match rest.chars().next() {
+                    Some(quote @ '"') | Some(quote @ '\'') => {
+                        let end = rest[1..].find(quote)? + 1;
+                        input = &rest[end + 1..];
+                        Some(&rest[1..end])
+                    }
+                    _ => {
+                        let end = rest
+                            .find(|char: char| char.is_ascii_whitespace() || char == '>')
+                            .unwrap_or(rest.len());
+                        input = &rest[end..];
+                        Some(&rest[..end])
+                    }
+                }
+            }
+            None => None,
+        };
+        attrs.push((name, value));
+    }
+}
+
+/// Skips the content and the end tag of a raw text element.
+fn skip_raw_text<'t>(input: &'t str, name: &str) -> &'t str {
+    for (index, _) in input.match_indices("</") {
+        let rest = &input[index + 2..];
+        let is_end_tag = rest
+            .get(..name.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(name))
+            && rest[name.len()..]
+                .chars()
+                .next()
+                .is_none_or(|char| is_tag_space(char) || char == '>');
+        if is_end_tag {
+            return skip_past(rest, '>');
+        }
+    }
+    ""
+}
+
+/// Decodes numeric character references and the most common named ones.
+///
+/// Other named references are kept as is, so they are displayed literally after escaping.
+fn decode(input: &str) -> Cow<'_, str> {
+    if !input.contains('&') {
+        return Cow::Borrowed(input);
+    }
+
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(pos) = rest.find('&') {
+        output.push_str(&rest[..pos]);
+        rest = &rest[pos + 1..];
+        // A reference is `#` or a letter followed by letters and digits, terminated by `;`
+        let start = usize::from(rest.starts_with('#'));
+        let decoded = rest[start..]
+            .find(|char: char| !char.is_ascii_alphanumeric())
+            .map(|end| start + end)
+            .filter(|&end| rest[end..].starts_with(';'))
+            .and_then(|end| {
+                let char = decode_reference(&rest[..end])?;
+                Some((char, end))
+            });
+        match decoded {
+            Some((char, end)) => {
+                output.push(char);
+                rest = &rest[end + 1..];
+            }
+            None => output.push('&'),
+        }
+    }
+    output.push_str(rest);
+    Cow::Owned(output)
+}
+
+fn decode_reference(reference: &str) -> Option<char> {
+    let number = if let Some(hex) = reference
+        .strip_prefix("#x")
+        .or_else(|| reference.strip_prefix("#X"))
+    {
+        u32::from_str_radix(hex, 16).ok()?
+    } else if let Some(decimal) = reference.strip_prefix('#') {
+        decimal.parse().ok()?
+    } else {
+        return match reference {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{a0}'),
+            _ => None,
+        };
+    };
+    Some(
+        char::from_u32(number)
+            .filter(|&char| char != '\0')
+            .unwrap_or('\u{fffd}'),
+    )
+}
@@ -0,0 +1,62 @@
+use std::fmt;
+
+use super::{escape_attr, trust, NoSpecial, Result, ToWholeHtmlAttr, TrustedAttr};
+
+/// Allows a type to be used as the value of an event handler attribute such as `onclick`.
+///
+/// The `html!` macro requires non-literal values of `on*` attributes to implement this trait,
+/// which is only implemented by `TrustedScript` and wrappers around it.
+pub trait ToScriptAttr {
+    /// Writes the whole attribute, **including the leading space before the attribute name**, to the
+    /// output.
+    fn fmt(&self, name: NoSpecial<'_>, f: &mut dyn fmt::Write) -> Result;
+}
+
+impl<T: ToScriptAttr + ?Sized> ToScriptAttr for &T {
+    #[inline]
+    fn fmt(&self, name: NoSpecial<'_>, f: &mut dyn fmt::Write) -> Result {
+        ToScriptAttr::fmt(&**self, name, f)
+    }
+}
+
+impl<T: ToScriptAttr> ToScriptAttr for Option<T> {
+    #[inline]
+    fn fmt(&self, name: NoSpecial<'_>, f: &mut dyn fmt::Write) -> Result {
+        match self {
+            Some(value) => ToScriptAttr::fmt(value, name, f),
+            None => Ok(()),
+        }
+    }
+}
+
+/// JavaScript code that is trusted to be used in event handler attributes such as `onclick`.
+///
+/// The code is still escaped as an attribute value.
+/// Never wrap user input with this struct.
+///
+/// This is also the only value type allowed for `dyn` attributes whose name turns out to be an
+/// event handler at runtime.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TrustedScript<T: AsRef<str>>(pub T);
+
+impl<T: AsRef<str>> ToScriptAttr for TrustedScript<T> {
+    #[inline]
+    fn fmt(&self, name: NoSpecial<'_>, f: &mut dyn fmt::Write) -> Result {
+        write!(f, " {}=\"", name.0)?;
+        escape_attr(self.0.as_ref(), f)?;
+        write!(f, "\"")?;
+        Ok(())
+    }
+}
+
+impl<T: AsRef<str>> ToWholeHtmlAttr for TrustedScript<T> {
+    #[inline]
+    fn fmt(&self, name: NoSpecial<'_>, f: &mut dyn fmt::Write) -> Result {
+        ToScriptAttr::fmt(self, name, f)
+    }
+
+    #[inline]
+    fn trust(&self, _: trust::Token) -> trust::Trust {
+        <Self as TrustedAttr>::TRUST
+    }
+}
@@ -0,0 +1,64 @@
+use super::{ToUrlAttr, ToWholeHtmlAttr, TrustedScript, TrustedUrl, UrlAttr};
+
+/// A value that may be used for a `dyn` attribute whose name turns out to be sensitive at runtime.
+///
+/// Values for event handler attributes must be a `TrustedScript`,
+/// and values for URL attributes must be a `UrlAttr` or a `TrustedUrl`.
+///
+/// This trait is sealed and cannot be implemented outside this crate.
+/// Other types also cannot claim to be trusted through `ToWholeHtmlAttr`:
+///
+/// ```compile_fail
+/// struct Evil;
+///
+/// impl minihtml::ToWholeHtmlAttr for Evil {
+///     fn fmt(&self, _: minihtml::NoSpecial, _: &mut dyn std::fmt::Write) -> minihtml::Result {
+///         Ok(())
+///     }
+///
+///     fn trust(&self, _: minihtml::trust::Token) -> minihtml::trust::Trust {
+///         minihtml::trust::Trust::Script
+///     }
+/// }
+/// ```
+pub trait TrustedAttr: ToWholeHtmlAttr + Sealed {
+    #[doc(hidden)]
+    const TRUST: Trust;
+}
+
+/// Prevents implementations of `TrustedAttr` outside this crate.
+pub trait Sealed {}
+
+/// The kind of sensitive attribute that a value is trusted for.
+///
+/// Returned by `ToWholeHtmlAttr::trust`, which cannot be overridden outside this crate
+/// because `Token` cannot be named there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trust {
+    None,
+    Script,
+    Url,
+}
+
+/// The argument of `ToWholeHtmlAttr::trust`.
+pub struct Token(pub(crate) ());
+
+impl<T: AsRef<str>> Sealed for TrustedScript<T> {}
+impl<T: AsRef<str>> TrustedAttr for TrustedScript<T> {
+    const TRUST: Trust = Trust::Script;
+}
+
+impl<T: AsRef<str>> Sealed for TrustedUrl<T> {}
+impl<T: AsRef<str>> TrustedAttr for TrustedUrl<T> {
+    const TRUST: Trust = Trust::Url;
+}
+
+impl<T: ToUrlAttr + ?Sized> Sealed for UrlAttr<&T> {}
+impl<T: ToUrlAttr + ?Sized> TrustedAttr for UrlAttr<&T> {
+    const TRUST: Trust = Trust::Url;
+}
+
+impl<T: ToUrlAttr> Sealed for UrlAttr<&Option<T>> {}
+impl<T: ToUrlAttr> TrustedAttr for UrlAttr<&Option<T>> {
+    const TRUST: Trust = Trust::Url;
+}
@@ -0,0 +1,156 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use super::{escape_attr, trust, NoSpecial, Result, ToWholeHtmlAttr, TrustedAttr};
+
+/// The value written in place of a URL with a disallowed scheme.
+pub const INVALID_URL: &str = "about:invalid";
+
+/// Allows a type to be used as the value of a URL attribute, i.e. one listed in `URL_ATTRS`
+/// such as `href` and `src`.
+///
+/// The `html!` macro wraps non-literal values of these attributes in `UrlAttr`, so only types
+/// implementing this trait can be used.
+pub trait ToUrlAttr {
+    /// Writes the escaped URL into `f`.
+    ///
+    /// URLs that do not satisfy `is_safe_url` must be replaced with `INVALID_URL`,
+    /// unless they are explicitly trusted.
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result;
+}
+
+impl<T: ToUrlAttr + ?Sized> ToUrlAttr for &T {
+    #[inline]
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        ToUrlAttr::fmt(&**self, f)
+    }
+}
+
+macro_rules! impl_deref {
+    ($($ty:ty),*) => {$(
+        impl<T: ToUrlAttr + ?Sized> ToUrlAttr for $ty {
+            #[inline]
+            fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+                ToUrlAttr::fmt(&**self, f)
+            }
+        }
+    )*};
+}
+
+impl_deref!(Box<T>, Rc<T>, Arc<T>);
+
+impl ToUrlAttr for str {
+    #[inline]
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        if is_safe_url(self) {
+            escape_attr(self, f)
+        } else {
+            f.write_str(INVALID_URL)
+        }
+    }
+}
+
+impl ToUrlAttr for String {
+    #[inline]
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        ToUrlAttr::fmt(self.as_str(), f)
+    }
+}
+
+impl<'t> ToUrlAttr for Cow<'t, str> {
+    #[inline]
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        ToUrlAttr::fmt(&**self, f)
+    }
+}
+
+/// Writes the wrapped URL without checking its scheme.
+///
+/// The URL is still escaped.
+/// Never wrap user input with this struct.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TrustedUrl<T: AsRef<str>>(pub T);
+
+impl<T: AsRef<str>> ToUrlAttr for TrustedUrl<T> {
+    #[inline]
+    fn fmt(&self, f: &mut dyn fmt::Write) -> Result {
+        escape_attr(self.0.as_ref(), f)
+    }
+}
+
+impl<T: AsRef<str>> ToWholeHtmlAttr for TrustedUrl<T> {
+    #[inline]
+    fn fmt(&self, name: NoSpecial<'_>, f: &mut dyn fmt::Write) -> Result {
+        ToWholeHtmlAttr::fmt(&UrlAttr(self), name, f)
+    }
+
+    #[inline]
+    fn trust(&self, _: trust::Token) -> trust::Trust {
+        <Self as TrustedAttr>::TRUST
+    }
+}
+
+/// Writes a URL attribute.
+///
+/// This is used by the `html!` macro for the attributes in `URL_ATTRS`.
+/// `None` omits the attribute like other attribute values.
+///
+/// The value of a `dyn` attribute whose name is a URL attribute at runtime
+/// must be wrapped in this struct, unless it is a `TrustedUrl`.
+#[derive(Debug, Clone, Copy)]
+pub struct UrlAttr<T>(pub T);
+
+impl<T: ToUrlAttr + ?Sized> ToWholeHtmlAttr for UrlAttr<&T> {
+    #[inline]
+    fn fmt(&self, name: NoSpecial<'_>, f: &mut dyn fmt::Write) -> Result {
+        write!(f, " {}=\"", name.0)?;
+        ToUrlAttr::fmt(self.0, f)?;
+        write!(f, "\"")?;
+        Ok(())
+    }
+
+    #[inline]
+    fn trust(&self, _: trust::Token) -> trust::Trust {
+        <Self as TrustedAttr>::TRUST
+    }
+}
+
+impl<T: ToUrlAttr> ToWholeHtmlAttr for UrlAttr<&Option<T>> {
+    #[inline]
+    fn fmt(&self, name: NoSpecial<'_>, f: &mut dyn fmt::Write) -> Result {
+        match self.0 {
+            Some(value) => ToWholeHtmlAttr::fmt(&UrlAttr(value), name, f),
+            None => Ok(()),
+        }
+    }
+
+    #[inline]
+    fn trust(&self, _: trust::Token) -> trust::Trust {
+        <Self as TrustedAttr>::TRUST
+    }
+}
+
+/// Checks whether the URL is relative or uses one of the schemes
+/// `http`, `https`, `mailto` and `tel`.
+///
+/// Leading whitespace and control characters, as well as tabs and newlines anywhere,
+/// are ignored in the same way as browsers do, so `" java\tscript:"` is detected as `javascript:`.
+pub fn is_safe_url(url: &str) -> bool {
+    let mut scheme = String::new();
+    let chars = url
+        .trim_start_matches(|char: char| char <= ' ')
+        .chars()
+        .filter(|&char| char != '\t' && char != '\n' && char != '\r');
+    for char in chars {
+        match char {
+            ':' => return matches!(scheme.as_str(), "http" | "https" | "mailto" | "tel"),
+            // a relative URL whose path, query or fragment contains a colon
+            '/' | '?' | '#' => return true,
+            _ => scheme.push(char.to_ascii_lowercase()),
+        }
+    }
+    // a relative URL without colons
+    true
+}
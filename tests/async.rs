@@ -0,0 +1,95 @@
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use minihtml::render_async;
+use tokio::io::AsyncWrite;
+
+#[derive(Clone, Default)]
+struct Shared(Arc<Mutex<Vec<u8>>>);
+
+impl Shared {
+    fn contents(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+impl AsyncWrite for Shared {
+    fn poll_write(self: Pin<&mut Self>, _: &mut Context, buf: &[u8]) -> Poll<io::Result<usize>> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[tokio::test]
+async fn test_render_async() {
+    let shared = Shared::default();
+    let observer = shared.clone();
+    let load_body = || async { observer.contents() };
+    let items = ["a", "b"];
+
+    let mut writer = shared.clone();
+    render_async!(&mut writer,
+        html {
+            head { title { +"Title" } }
+            body {
+                p { +load_body().await }
+                @for item in &items {
+                    i { +*item }
+                }
+            }
+        }
+    )
+    .await
+    .unwrap();
+
+    #[rustfmt::skip]
+    assert_eq!(shared.contents(), "<html>\
+        <head><title>Title</title></head>\
+        <body>\
+            <p>&lt;html&gt;&lt;head&gt;&lt;title&gt;Title&lt;/title&gt;&lt;/head&gt;&lt;body&gt;&lt;p&gt;</p>\
+            <i>a</i><i>b</i>\
+        </body>\
+    </html>");
+}
+
+#[tokio::test]
+async fn test_write_to_vec() {
+    let mut buf = vec![];
+    render_async!(&mut buf, p { +async { 1 }.await })
+        .await
+        .unwrap();
+    assert_eq!(buf, b"<p>1</p>");
+}
+
+#[tokio::test]
+async fn test_nested_await() {
+    let shared = Shared::default();
+    let observer = shared.clone();
+    let load = || async { Ok::<_, std::fmt::Error>(observer.contents().len()) };
+
+    let mut writer = shared.clone();
+    render_async!(&mut writer, p { +load().await? } p { +(load().await.unwrap() + 1) })
+        .await
+        .unwrap();
+    assert_eq!(shared.contents(), "<p>3</p><p>12</p>");
+}
+
+#[tokio::test]
+async fn test_attr_policy() {
+    let mut buf = vec![];
+    let name = "a b";
+    render_async!(&mut buf, #![attr_policy(invalid = Skip)] p(dyn name = 1) { +"x" })
+        .await
+        .unwrap();
+    assert_eq!(buf, b"<p>x</p>");
+}
@@ -0,0 +1,94 @@
+use std::panic;
+
+use minihtml::{try_html_string, AttrName, InvalidAttrName};
+
+#[test]
+fn test_invalid_attr_policy() {
+    let bad = "a\"b";
+    let handler = "onload";
+    assert!(try_html_string! { div(dyn bad = "1", title = "t"); }.is_err());
+    assert!(try_html_string! { div(dyn handler = "1"); }.is_err());
+    assert!(try_html_string! {
+        #![attr_policy(invalid = Error)]
+        div(dyn bad = "1");
+    }
+    .is_err());
+
+    assert_eq!(
+        try_html_string! {
+            #![attr_policy(invalid = Skip)]
+            div(dyn bad = "1", title = "t", dyn handler = "1");
+        }
+        .unwrap(),
+        "<div title=\"t\"/>"
+    );
+
+    let result = panic::catch_unwind(|| {
+        try_html_string! {
+            #![attr_policy(invalid = Panic)]
+            div(dyn bad = "1");
+        }
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_attr_name() {
+    assert_eq!(AttrName::new("data-x").unwrap().as_str(), "data-x");
+    assert!(AttrName::new("onclick").unwrap().is_event_handler());
+    assert!(AttrName::new("ONLOAD").unwrap().is_event_handler());
+    assert!(!AttrName::new("data-onclick").unwrap().is_event_handler());
+    assert_eq!(
+        AttrName::new("a b"),
+        Err(InvalidAttrName(String::from("a b")))
+    );
+
+    let name = AttrName::new("data-y").unwrap();
+    assert_eq!(
+        try_html_string! { div(dyn name = 1); }.unwrap(),
+        "<div data-y=\"1\"/>"
+    );
+}
+
+#[test]
+fn test_duplicate_attr_policy() {
+    let a = "data-x";
+    let b = "DATA-X";
+    let title = "title";
+    assert!(try_html_string! { div(dyn a = 1, dyn b = 2); }.is_err());
+    assert!(try_html_string! { div(title = "t", dyn title = 1); }.is_err());
+    assert!(try_html_string! { div.c(dyn "class" = "d"); }.is_err());
+
+    assert_eq!(
+        try_html_string! {
+            #![attr_policy(duplicate = LastWins)]
+            div(dyn a = 1, title = "t", dyn b = 2, dyn title = 3);
+        }
+        .unwrap(),
+        "<div title=\"t\" DATA-X=\"2\"/>"
+    );
+    assert_eq!(
+        try_html_string! {
+            #![attr_policy(duplicate = FirstWins)]
+            div(dyn a = 1, title = "t", dyn b = 2, dyn title = 3);
+        }
+        .unwrap(),
+        "<div data-x=\"1\" title=\"t\"/>"
+    );
+}
+
+#[test]
+fn test_combined_attr_policy() {
+    let a = "data-x";
+    let bad = "a b";
+    assert_eq!(
+        try_html_string! {
+            #![attr_policy(invalid = Skip, duplicate = FirstWins)]
+            div(dyn a = 1, dyn bad = 2, dyn a = 3) {
+                p(dyn a = 4, dyn a = 5);
+            }
+        }
+        .unwrap(),
+        "<div data-x=\"1\"><p data-x=\"4\"/></div>"
+    );
+}
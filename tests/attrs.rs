@@ -0,0 +1,135 @@
+use minihtml::html_string_or_panic;
+
+#[test]
+fn test_source_order() {
+    let ret = html_string_or_panic! {
+        a(z = "1", href = "/", y = "2", b = "3", download);
+    };
+    assert_eq!(
+        ret.as_str(),
+        "<a z=\"1\" href=\"/\" y=\"2\" b=\"3\" download/>"
+    );
+}
+
+#[test]
+fn test_shorthand_order() {
+    let ret = html_string_or_panic! {
+        div.foo.bar #main(title = "t", data-x = "1");
+    };
+    assert_eq!(
+        ret.as_str(),
+        "<div id=\"main\" class=\"foo bar\" title=\"t\" data-x=\"1\"/>"
+    );
+}
+
+#[test]
+fn test_explicit_class_merged() {
+    let extra = "baz";
+    let ret = html_string_or_panic! {
+        div.foo(title = "t", class = extra);
+    };
+    assert_eq!(ret.as_str(), "<div class=\"foo baz\" title=\"t\"/>");
+}
+
+#[test]
+fn test_dyn_order() {
+    let name = String::from("data-dyn");
+    let ret = html_string_or_panic! {
+        div(a = "1", dyn name.as_str() = "2", b = "3");
+    };
+    assert_eq!(ret.as_str(), "<div a=\"1\" data-dyn=\"2\" b=\"3\"/>");
+}
+
+#[test]
+fn test_static_values() {
+    let ret = html_string_or_panic! {
+        input.a(class = "b", value = "\"x\" & y", checked = true, disabled = false, required);
+        div.a(class = "");
+    };
+    assert_eq!(
+        ret.as_str(),
+        "<input class=\"a b\" value=\"&quot;x&quot; &amp; y\" checked required/><div class=\"a\"/>"
+    );
+}
+
+#[test]
+fn test_event_handlers() {
+    use minihtml::TrustedScript;
+
+    let script = TrustedScript(String::from("go(\"a & b\")"));
+    let none: Option<TrustedScript<&str>> = None;
+    let label = "x";
+    let ret = html_string_or_panic! {
+        button(onclick = &script, onblur = none, onfocus = "go()", data-onclick = label);
+    };
+    assert_eq!(
+        ret.as_str(),
+        "<button onclick=\"go(&quot;a &amp; b&quot;)\" onfocus=\"go()\" data-onclick=\"x\"/>"
+    );
+}
+
+#[test]
+fn test_dyn_checks() {
+    use minihtml::{try_html_string, TrustedScript};
+
+    let onclick = "onclick";
+    assert!(try_html_string! { button(dyn onclick = "alert(1)"); }.is_err());
+    assert_eq!(
+        try_html_string! { button(dyn onclick = TrustedScript("go()")); }.unwrap(),
+        "<button onclick=\"go()\"/>"
+    );
+
+    for name in &["", "a b", "a\"b", "a>b", "a/b", "a=b", "a\u{0}b"] {
+        assert!(
+            try_html_string! { div(dyn *name = "1"); }.is_err(),
+            "{:?}",
+            name
+        );
+    }
+    let name = "data-ok";
+    assert_eq!(
+        try_html_string! { div(dyn name = "1"); }.unwrap(),
+        "<div data-ok=\"1\"/>"
+    );
+}
+
+#[test]
+fn test_explicit_class_escaped() {
+    let evil = "x\" onmouseover=\"alert(1)";
+    let ret = html_string_or_panic! {
+        div.foo(class = evil);
+    };
+    assert_eq!(
+        ret.as_str(),
+        "<div class=\"foo x&quot; onmouseover=&quot;alert(1)\"/>"
+    );
+}
+
+#[test]
+fn test_dyn_evaluation_order() {
+    use std::cell::RefCell;
+
+    let log = RefCell::new(vec![]);
+    let eval = |value: &'static str| {
+        log.borrow_mut().push(value);
+        value
+    };
+    let ret = html_string_or_panic! {
+        div.a(
+            title = eval("t"),
+            dyn eval("data-x") = eval("x"),
+            class = eval("c"),
+            dyn eval("data-y") = eval("y"),
+        ) {
+            p(dyn eval("data-z") = eval("z"));
+        }
+    };
+    assert_eq!(
+        ret,
+        "<div class=\"a c\" title=\"t\" data-x=\"x\" data-y=\"y\"><p data-z=\"z\"/></div>"
+    );
+    assert_eq!(
+        *log.borrow(),
+        ["t", "data-x", "x", "c", "data-y", "y", "data-z", "z"]
+    );
+}
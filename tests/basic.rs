@@ -1,10 +1,10 @@
-use minihtml::html_string;
+use minihtml::html_string_or_panic;
 
 #[test]
 fn test_basic() {
     let variable = "quz qux";
     let is_enabled = false;
-    let ret: String = html_string! {
+    let ret: String = html_string_or_panic! {
         html {
             head {
                 title { +"Test title" };
@@ -17,7 +17,7 @@ fn test_basic() {
         }
     };
 
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+    #[rustfmt::skip]
     assert_eq!(ret.as_str(), "<html>\
         <head>\
             <title>Test title</title>\
@@ -29,3 +29,15 @@ fn test_basic() {
         </body>\
     </html>");
 }
+
+#[test]
+fn test_dyn_attr() {
+    let name = "data-value";
+    let ret: String = html_string_or_panic! {
+        div(id = "x", dyn name = "y", dyn "hidden") { +"z" };
+    };
+    assert_eq!(
+        ret.as_str(),
+        "<div id=\"x\" data-value=\"y\" hidden>z</div>"
+    );
+}
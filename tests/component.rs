@@ -0,0 +1,121 @@
+use std::fmt;
+
+use minihtml::{html, html_string_or_panic, Component, ToHtmlNode};
+
+struct Card<'t> {
+    title: &'t str,
+    footer: Option<&'t str>,
+}
+
+impl<'t> Component for Card<'t> {
+    fn fmt(&self, children: &dyn ToHtmlNode, f: &mut dyn fmt::Write) -> minihtml::Result {
+        let title = self.title;
+        let footer = self.footer;
+        ToHtmlNode::fmt(
+            &html! {
+                div.card {
+                    h2 { +title }
+                    +children
+                    @if let Some(footer) = footer {
+                        footer { +footer }
+                    }
+                }
+            },
+            f,
+        )
+    }
+}
+
+struct Separator;
+
+impl Component for Separator {
+    fn fmt(&self, _: &dyn ToHtmlNode, f: &mut dyn fmt::Write) -> minihtml::Result {
+        ToHtmlNode::fmt(&html!(hr.separator), f)
+    }
+}
+
+#[test]
+fn test_component() {
+    let footer = Some("end");
+    let items = ["a", "b"];
+    let ret = html_string_or_panic! {
+        @Card(title = "Items", footer) {
+            @for item in &items {
+                p { +*item }
+            }
+        }
+        @Separator;
+        @Card(title = "Empty", footer = None);
+    };
+    #[rustfmt::skip]
+    assert_eq!(ret.as_str(), "<div class=\"card\">\
+        <h2>Items</h2>\
+        <p>a</p>\
+        <p>b</p>\
+        <footer>end</footer>\
+    </div>\
+    <hr class=\"separator\"/>\
+    <div class=\"card\">\
+        <h2>Empty</h2>\
+    </div>");
+}
+
+struct Layout<'t, H: ToHtmlNode, S: ToHtmlNode> {
+    title: &'t str,
+    head: H,
+    sidebar: S,
+}
+
+impl<'t, H: ToHtmlNode, S: ToHtmlNode> Component for Layout<'t, H, S> {
+    fn fmt(&self, children: &dyn ToHtmlNode, f: &mut dyn fmt::Write) -> minihtml::Result {
+        let Layout {
+            title,
+            head,
+            sidebar,
+        } = self;
+        ToHtmlNode::fmt(
+            &html! {
+                html {
+                    head {
+                        title { +*title }
+                        +head
+                    }
+                    body {
+                        nav { +sidebar }
+                        main { +children }
+                    }
+                }
+            },
+            f,
+        )
+    }
+}
+
+#[test]
+fn test_slots() {
+    let links = ["/a", "/b"];
+    let ret = html_string_or_panic! {
+        @Layout(title = "Page") {
+            @slot sidebar {
+                @for link in &links {
+                    a(href = *link);
+                }
+            }
+            p { +"body" }
+            @slot head {
+                meta(charset = "utf-8");
+            }
+        }
+    };
+    #[rustfmt::skip]
+    assert_eq!(ret.as_str(), "<html>\
+        <head>\
+            <title>Page</title>\
+            <meta charset=\"utf-8\"/>\
+        </head>\
+        <body>\
+            <nav><a href=\"/a\"/><a href=\"/b\"/></nav>\
+            <main><p>body</p></main>\
+        </body>\
+    </html>");
+}
@@ -0,0 +1,128 @@
+use minihtml::html_string_or_panic;
+
+fn status(logged_in: bool, name: Option<&str>) -> String {
+    html_string_or_panic! {
+        div {
+            @if !logged_in {
+                a(href = "/login") { +"Log in" }
+            } @else if let Some(name) = name {
+                span { +"Welcome, "; +name }
+            } @else {
+                span { +"Welcome" }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_if_else() {
+    assert_eq!(
+        status(false, None).as_str(),
+        "<div><a href=\"/login\">Log in</a></div>"
+    );
+    assert_eq!(
+        status(true, Some("a&b")).as_str(),
+        "<div><span>Welcome, a&amp;b</span></div>"
+    );
+    assert_eq!(
+        status(true, None).as_str(),
+        "<div><span>Welcome</span></div>"
+    );
+}
+
+#[test]
+fn test_if_without_else() {
+    let show = false;
+    let ret = html_string_or_panic! {
+        @if show { br; }
+        hr;
+    };
+    assert_eq!(ret.as_str(), "<hr/>");
+}
+
+#[test]
+fn test_for() {
+    let rows = vec![("a", 1), ("b<", 2)];
+    let ret = html_string_or_panic! {
+        table {
+            @for (name, value) in &rows {
+                tr {
+                    td { +*name }
+                    td { +value.to_string().as_str() }
+                }
+            }
+        }
+    };
+    #[rustfmt::skip]
+    assert_eq!(ret.as_str(), "<table>\
+        <tr><td>a</td><td>1</td></tr>\
+        <tr><td>b&lt;</td><td>2</td></tr>\
+    </table>");
+}
+
+#[test]
+fn test_for_empty() {
+    let items: Vec<&str> = vec![];
+    let ret = html_string_or_panic! {
+        ul {
+            @for item in &items { li { +*item } }
+        }
+    };
+    assert_eq!(ret.as_str(), "<ul></ul>");
+}
+
+enum Status {
+    Active,
+    Suspended { reason: &'static str },
+    Deleted,
+}
+
+fn render_status(status: &Status) -> String {
+    html_string_or_panic! {
+        @match status {
+            Status::Active => { span.ok { +"active" } }
+            Status::Suspended { reason } if reason.is_empty() => {
+                span.warn { +"suspended" }
+            }
+            Status::Suspended { reason } => {
+                span.warn { +"suspended: "; +*reason }
+            }
+            Status::Deleted => {}
+        }
+    }
+}
+
+#[test]
+fn test_match() {
+    assert_eq!(
+        render_status(&Status::Active).as_str(),
+        "<span class=\"ok\">active</span>"
+    );
+    assert_eq!(
+        render_status(&Status::Suspended { reason: "" }).as_str(),
+        "<span class=\"warn\">suspended</span>"
+    );
+    assert_eq!(
+        render_status(&Status::Suspended { reason: "spam" }).as_str(),
+        "<span class=\"warn\">suspended: spam</span>"
+    );
+    assert_eq!(render_status(&Status::Deleted).as_str(), "");
+}
+
+#[test]
+fn test_let() {
+    let user = ("sofe", 42);
+    let ret = html_string_or_panic! {
+        div {
+            @let url = format!("/users/{}", user.1);
+            @let (name, _) = user;
+            a(href = url.as_str()) { +name }
+        }
+        @let url = "/home";
+        a(href = url);
+    };
+    assert_eq!(
+        ret.as_str(),
+        "<div><a href=\"/users/42\">sofe</a></div><a href=\"/home\"/>"
+    );
+}
@@ -0,0 +1,97 @@
+use std::fmt::{self, Write};
+
+use minihtml::{escape_attr, escape_text};
+use proptest::prelude::*;
+
+/// The per-character escaper that `escape_text` and `escape_attr` replaced,
+/// which is still used with the `strict-escaping` feature.
+#[cfg(feature = "strict-escaping")]
+struct Reference<'t>(&'t str);
+
+#[cfg(feature = "strict-escaping")]
+impl<'t> fmt::Display for Reference<'t> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for char in self.0.chars() {
+            let escape = match char {
+                '&' => "&amp;",
+                '<' => "&lt;",
+                '>' => "&gt;",
+                '\'' => "&apos;",
+                '"' => "&quot;",
+                _ => {
+                    write!(f, "{}", char)?;
+                    continue;
+                }
+            };
+            write!(f, "{}", escape)?;
+        }
+        Ok(())
+    }
+}
+
+fn run(escape: fn(&str, &mut dyn Write) -> fmt::Result, s: &str) -> String {
+    let mut output = String::new();
+    escape(s, &mut output).unwrap();
+    output
+}
+
+fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&apos;", "'")
+        .replace("&quot;", "\"")
+        .replace("&amp;", "&")
+}
+
+#[test]
+#[cfg(not(feature = "strict-escaping"))]
+fn test_escape() {
+    assert_eq!(run(escape_text, ""), "");
+    assert_eq!(run(escape_text, "plain"), "plain");
+    assert_eq!(
+        run(escape_text, "<a href=\"x\">'&'</a>"),
+        "&lt;a href=\"x\">'&amp;'&lt;/a>"
+    );
+    assert_eq!(
+        run(escape_attr, "<a href=\"x\">'&'</a>"),
+        "<a href=&quot;x&quot;>'&amp;'</a>"
+    );
+    assert_eq!(run(escape_attr, "é&ü"), "é&amp;ü");
+}
+
+#[test]
+#[cfg(feature = "strict-escaping")]
+fn test_escape_strict() {
+    assert_eq!(run(escape_text, ""), "");
+    assert_eq!(run(escape_text, "plain"), "plain");
+    assert_eq!(
+        run(escape_text, "<a href=\"x\">'&'</a>"),
+        "&lt;a href=&quot;x&quot;&gt;&apos;&amp;&apos;&lt;/a&gt;"
+    );
+    assert_eq!(run(escape_attr, "é&ü"), "é&amp;ü");
+}
+
+proptest! {
+    #[test]
+    fn prop_escape_text_roundtrip(s in "(\\PC|[&<>'\"])*") {
+        let escaped = run(escape_text, &s);
+        prop_assert!(!escaped.contains('<'));
+        prop_assert_eq!(unescape(&escaped), s);
+    }
+
+    #[test]
+    fn prop_escape_attr_roundtrip(s in "(\\PC|[&<>'\"])*") {
+        let escaped = run(escape_attr, &s);
+        prop_assert!(!escaped.contains('"'));
+        prop_assert_eq!(unescape(&escaped), s);
+    }
+}
+
+#[cfg(feature = "strict-escaping")]
+proptest! {
+    #[test]
+    fn prop_strict_matches_reference(s in "(\\PC|[&<>'\"])*") {
+        prop_assert_eq!(run(escape_text, &s), Reference(&s).to_string());
+        prop_assert_eq!(run(escape_attr, &s), Reference(&s).to_string());
+    }
+}
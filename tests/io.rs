@@ -0,0 +1,56 @@
+use std::io;
+
+use minihtml::{html, render_to_io, ToHtmlNode};
+
+#[test]
+fn test_render_to_io() {
+    let items = ["a", "b<"];
+    let node = html! {
+        ul {
+            @for item in &items { li { +*item } }
+        }
+    };
+    let mut buf = vec![];
+    render_to_io(&node, &mut buf).unwrap();
+    assert_eq!(
+        String::from_utf8(buf).unwrap(),
+        "<ul><li>a</li><li>b&lt;</li></ul>"
+    );
+}
+
+struct Full(usize);
+
+impl io::Write for Full {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.0 < buf.len() {
+            return Err(io::Error::new(io::ErrorKind::WriteZero, "full"));
+        }
+        self.0 -= buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_io_error() {
+    let node = html! { p { +"some long text" } };
+    let err = render_to_io(&node, &mut Full(8)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+}
+
+struct Failing;
+
+impl ToHtmlNode for Failing {
+    fn fmt(&self, _: &mut dyn std::fmt::Write) -> minihtml::Result {
+        Err(std::fmt::Error)
+    }
+}
+
+#[test]
+fn test_node_error() {
+    let err = render_to_io(&Failing, &mut Vec::new()).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+}
@@ -0,0 +1,156 @@
+use minihtml::{html_string_or_panic, PreEscaped, Raw};
+
+#[test]
+fn test_raw() {
+    let rendered = "<em>a &amp; b</em>";
+    let cached: PreEscaped = Raw(String::from("<br/>"));
+    let ret = html_string_or_panic! {
+        div { +&Raw(rendered); +&cached; +"<br/>" }
+    };
+    let expected = if cfg!(feature = "strict-escaping") {
+        "<div><em>a &amp; b</em><br/>&lt;br/&gt;</div>"
+    } else {
+        "<div><em>a &amp; b</em><br/>&lt;br/></div>"
+    };
+    assert_eq!(ret.as_str(), expected);
+}
+
+#[test]
+fn test_numbers() {
+    let count = 3u32;
+    let offset = -12i64;
+    let ratio = 0.5f64;
+    let ret = html_string_or_panic! {
+        span(data-count = count, data-offset = offset) { +count; +' '; +offset; +' '; +ratio }
+        input(value = ratio);
+    };
+    assert_eq!(
+        ret.as_str(),
+        "<span data-count=\"3\" data-offset=\"-12\">3 -12 0.5</span><input value=\"0.5\"/>"
+    );
+}
+
+#[test]
+fn test_std_types() {
+    use std::borrow::Cow;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    let owned = String::from("a<b");
+    let cow: Cow<str> = Cow::Borrowed("&");
+    let boxed: Box<str> = "\"".into();
+    let rc = Rc::new(1u8);
+    let arc: Arc<str> = "'".into();
+    let ret = html_string_or_panic! {
+        p(title = owned) { +owned; +cow; +boxed; +rc; +arc; +'<' }
+    };
+    let expected = if cfg!(feature = "strict-escaping") {
+        "<p title=\"a&lt;b\">a&lt;b&amp;&quot;1&apos;&lt;</p>"
+    } else {
+        "<p title=\"a<b\">a&lt;b&amp;\"1'&lt;</p>"
+    };
+    assert_eq!(ret.as_str(), expected);
+}
+
+#[test]
+fn test_collections() {
+    use minihtml::Iter;
+
+    let some = Some("a");
+    let none: Option<&str> = None;
+    let vec = vec!["b", "c"];
+    let array = [1, 2];
+    let tuple = ("d", 3, Some('e'));
+    let ret = html_string_or_panic! {
+        p { +some; +none; +vec; +&vec[..1]; +array; +tuple }
+        p {
+            +Iter(vec.iter().map(|item| ("[", *item, "]")))
+        }
+    };
+    assert_eq!(ret.as_str(), "<p>abcb12d3e</p><p>[b][c]</p>");
+}
+
+#[test]
+fn test_text() {
+    use std::fmt;
+
+    use minihtml::Text;
+
+    struct Money(i64);
+
+    impl fmt::Display for Money {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "<{}.{:02}> & \"co\"", self.0 / 100, self.0 % 100)
+        }
+    }
+
+    let price = Money(1234);
+    let ret = html_string_or_panic! {
+        data(value = Text(&price)) { +Text(&price) }
+    };
+    let expected = if cfg!(feature = "strict-escaping") {
+        "<data value=\"&lt;12.34&gt; &amp; &quot;co&quot;\">&lt;12.34&gt; &amp; &quot;co&quot;</data>"
+    } else {
+        "<data value=\"<12.34> &amp; &quot;co&quot;\">&lt;12.34> &amp; \"co\"</data>"
+    };
+    assert_eq!(ret.as_str(), expected);
+}
+
+struct Failing;
+
+impl minihtml::ToHtmlNode for Failing {
+    fn fmt(&self, _: &mut dyn std::fmt::Write) -> minihtml::Result {
+        Err(std::fmt::Error)
+    }
+}
+
+#[test]
+fn test_try_html_string() {
+    use minihtml::try_html_string;
+
+    assert_eq!(try_html_string!(p { +"ok" }), Ok(String::from("<p>ok</p>")));
+    assert_eq!(try_html_string!(p { +Failing }), Err(std::fmt::Error));
+    assert_eq!(minihtml::render_to_string(&Failing), Err(std::fmt::Error));
+}
+
+#[test]
+#[should_panic]
+fn test_html_string_panics() {
+    let _ = html_string_or_panic!(p { +Failing });
+}
+
+#[test]
+fn test_size_hint() {
+    use minihtml::{html, ToHtmlNode};
+
+    let name = "world";
+    let node = html! {
+        div.greeting(title = "hello") {
+            +"Hello, "; +name
+            br;
+        }
+    };
+    let output = minihtml::render_to_string(&node).unwrap();
+    assert_eq!(node.size_hint(), output.len());
+
+    struct Overestimated;
+
+    impl ToHtmlNode for Overestimated {
+        fn fmt(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+            f.write_str("x")
+        }
+
+        fn size_hint(&self) -> usize {
+            64
+        }
+    }
+
+    let output = minihtml::render_to_string(&Overestimated).unwrap();
+    assert_eq!(output, "x");
+    assert!(output.capacity() >= 64);
+
+    let items = vec!["a", "b"];
+    assert_eq!(items.size_hint(), 2);
+    assert_eq!(Some(&items).size_hint(), 2);
+    assert_eq!(("abc", 'd', None::<&str>).size_hint(), 4);
+}
@@ -0,0 +1,54 @@
+use minihtml::{html_string_or_panic, render_to_string, try_html_string, Css, Raw};
+
+#[test]
+fn test_style() {
+    let user_css = "a::before { content: \"</STYLE><b><!--\" }";
+    let ret = html_string_or_panic! {
+        style { +"a { color: red; }"; +Css(user_css) }
+    };
+    assert_eq!(
+        ret.as_str(),
+        "<style>a { color: red; }a::before { content: \"\\3c /STYLE><b>\\3c !--\" }</style>"
+    );
+
+    let media = "@media (400px <= width < 600px) { a { color: blue; } }";
+    let ret = html_string_or_panic! {
+        style { +Css(media) }
+    };
+    assert_eq!(ret, format!("<style>{}</style>", media));
+}
+
+#[test]
+fn test_script() {
+    let inline = Raw("console.log(1 < 2 && 3 > 2);");
+    let ret = html_string_or_panic! {
+        script(src = "/app.js");
+        script { +"var x = '<b>';"; +&inline }
+    };
+    assert_eq!(
+        ret.as_str(),
+        "<script src=\"/app.js\"></script><script>var x = '<b>';console.log(1 < 2 && 3 > 2);</script>"
+    );
+}
+
+#[test]
+fn test_raw_closing_tag() {
+    assert!(try_html_string! { script { +Raw("x = '</Script>'") } }.is_err());
+    assert!(try_html_string! { style { +Raw("<!-- a {}") } }.is_err());
+    assert!(render_to_string(&Raw("a</b>")).is_ok());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_script_json() {
+    use minihtml::ScriptJson;
+
+    let data = vec!["</script><!--", "a & b"];
+    let ret = html_string_or_panic! {
+        script { +"var data = "; +ScriptJson(&data); +";" }
+    };
+    assert_eq!(
+        ret.as_str(),
+        r#"<script>var data = ["\u003c/script\u003e\u003c!--","a \u0026 b"];</script>"#
+    );
+}
@@ -0,0 +1,95 @@
+use minihtml::{html_string_or_panic, Sanitizer};
+
+fn sanitize(sanitizer: &Sanitizer, input: &str) -> String {
+    html_string_or_panic! { +sanitizer.sanitize(input) }
+}
+
+#[test]
+fn test_basic() {
+    let sanitizer = Sanitizer::basic();
+    assert_eq!(
+        sanitize(
+            &sanitizer,
+            "<P onclick=\"x()\">Hi <b>there</b><script>alert('</p>')</SCRIPT ><span>!</span></p>"
+        ),
+        "<p>Hi <b>there</b>!</p>"
+    );
+    assert_eq!(
+        sanitize(&sanitizer, "a<br>b<hr><img src=x onerror=alert(1)><br/>"),
+        "a<br/>b<br/>"
+    );
+    assert_eq!(
+        sanitize(
+            &sanitizer,
+            "x<!-- <b>hidden</b> -->y<!doctype html>z<!-- unterminated"
+        ),
+        "xyz"
+    );
+    assert_eq!(
+        sanitize(&sanitizer, "<!--><p>a</p><!---><b>b</b><!---->c"),
+        "<p>a</p><b>b</b>c"
+    );
+}
+
+#[test]
+fn test_links() {
+    let sanitizer = Sanitizer::basic();
+    assert_eq!(
+        sanitize(
+            &sanitizer,
+            "<a href=\" java&#x73;cript:alert(1)\" title='a\"b' title=c>x</a><a HREF=/ok?a=1&amp;b=2 id=y>y"
+        ),
+        "<a href=\"about:invalid\" title=\"a&quot;b\">x</a><a href=\"/ok?a=1&amp;b=2\">y</a>"
+    );
+}
+
+#[test]
+fn test_structure() {
+    let sanitizer = Sanitizer::basic();
+    assert_eq!(
+        sanitize(&sanitizer, "<b><i>x</b>y</i></ul>"),
+        "<b><i>x</i></b>y"
+    );
+    assert_eq!(
+        sanitize(&sanitizer, "<ul><li>a<li>b"),
+        "<ul><li>a<li>b</li></li></ul>"
+    );
+    assert_eq!(sanitize(&sanitizer, "<b title=\"unterminated>x"), "");
+}
+
+#[test]
+fn test_text() {
+    let sanitizer = Sanitizer::new();
+    assert_eq!(
+        sanitize(
+            &sanitizer,
+            "1 < 2 &amp; 3 &#60;b&#x3C; &unknown; &#0; <3 <b>x</b>"
+        ),
+        "1 &lt; 2 &amp; 3 &lt;b&lt; &amp;unknown; \u{fffd} &lt;3 x"
+    );
+    assert_eq!(
+        sanitize(&sanitizer, "&#42 fixed; a &# b; &#x; &#xZ1; &#X2a;"),
+        "&amp;#42 fixed; a &amp;# b; &amp;#x; &amp;#xZ1; *"
+    );
+}
+
+#[test]
+fn test_builder() {
+    let sanitizer = Sanitizer::new()
+        .allow_elements(&["SPAN", "img", "script"])
+        .allow_attributes("img", &["src", "alt", "onload"])
+        .allow_global_attributes(&["class"]);
+    assert_eq!(
+        sanitize(
+            &sanitizer,
+            "<span class=x id=y><em>z</em></span><img src=\"data:x\" alt=a class=c onload=f()><script>x</script>"
+        ),
+        "<span class=\"x\">z</span><img src=\"about:invalid\" alt=\"a\" class=\"c\"/>"
+    );
+}
+
+#[test]
+#[should_panic(expected = "not a valid attribute name")]
+fn test_builder_invalid_name() {
+    let _ = Sanitizer::new().allow_global_attributes(&["a b"]);
+}
@@ -0,0 +1,48 @@
+use std::fmt;
+
+use minihtml::{render, try_html};
+
+#[derive(Debug, PartialEq)]
+enum Error {
+    Fmt,
+    NotFound(u32),
+}
+
+impl From<fmt::Error> for Error {
+    fn from(_: fmt::Error) -> Self {
+        Error::Fmt
+    }
+}
+
+fn lookup(id: u32) -> Result<&'static str, Error> {
+    match id {
+        1 => Ok("one"),
+        2 => Ok("two"),
+        _ => Err(Error::NotFound(id)),
+    }
+}
+
+fn page(ids: &[u32]) -> Result<String, Error> {
+    render(&try_html! {
+        ul {
+            @for &id in ids {
+                li(title = lookup(id)?) { +lookup(id)? }
+            }
+        }
+    })
+}
+
+#[test]
+fn test_ok() {
+    assert_eq!(
+        page(&[1, 2]),
+        Ok(String::from(
+            "<ul><li title=\"one\">one</li><li title=\"two\">two</li></ul>"
+        ))
+    );
+}
+
+#[test]
+fn test_err() {
+    assert_eq!(page(&[1, 3, 2]), Err(Error::NotFound(3)));
+}
@@ -0,0 +1,92 @@
+use minihtml::{html_string_or_panic, is_safe_url, TrustedUrl};
+
+#[test]
+fn test_is_safe_url() {
+    for url in &[
+        "",
+        "/path",
+        "page.html",
+        "?q=a:b",
+        "#top",
+        "./a:b",
+        "//example.com/",
+        "http://example.com/",
+        "HTTPS://example.com/",
+        "mailto:a@example.com",
+        "tel:+123",
+    ] {
+        assert!(is_safe_url(url), "{:?}", url);
+    }
+    for url in &[
+        "javascript:alert(1)",
+        "JavaScript:alert(1)",
+        " \u{1}javascript:alert(1)",
+        "java\tscr\nipt:alert(1)",
+        "vbscript:msgbox",
+        "data:text/html,<script>",
+    ] {
+        assert!(!is_safe_url(url), "{:?}", url);
+    }
+}
+
+#[test]
+fn test_url_attrs() {
+    let evil = "javascript:alert(1)";
+    let link = String::from("/a?b=1&c=2");
+    let none: Option<&str> = None;
+    let ret = html_string_or_panic! {
+        a(href = evil) { +"x" }
+        a(href = &link, title = evil);
+        img(src = Some(evil));
+        form(action = none);
+        a(href = TrustedUrl(evil));
+        a(href = "javascript:void(0)");
+        video(poster = evil);
+        object(data = evil);
+        blockquote(cite = &link, data - x = evil);
+    };
+    assert_eq!(
+        ret.as_str(),
+        "<a href=\"about:invalid\">x</a>\
+         <a href=\"/a?b=1&amp;c=2\" title=\"javascript:alert(1)\"/>\
+         <img src=\"about:invalid\"/>\
+         <form/>\
+         <a href=\"javascript:alert(1)\"/>\
+         <a href=\"javascript:void(0)\"/>\
+         <video poster=\"about:invalid\"/>\
+         <object data=\"about:invalid\"/>\
+         <blockquote cite=\"/a?b=1&amp;c=2\" data-x=\"javascript:alert(1)\"/>"
+    );
+}
+
+#[test]
+fn test_dyn_url_attrs() {
+    use minihtml::{try_html_string, UrlAttr};
+
+    let href = "href";
+    let evil = "javascript:alert(1)";
+    let link = "/a?b=1&c=2";
+    assert!(try_html_string! { a(dyn href = evil); }.is_err());
+    assert!(try_html_string! { a(dyn "SRC" = link); }.is_err());
+    assert!(try_html_string! { a(dyn "xlink:href" = link); }.is_err());
+    assert_eq!(
+        try_html_string! { a(dyn "xlink:href" = UrlAttr(link)); }.unwrap(),
+        "<a xlink:href=\"/a?b=1&amp;c=2\"/>"
+    );
+    assert_eq!(
+        try_html_string! { a(dyn href = UrlAttr(evil)); }.unwrap(),
+        "<a href=\"about:invalid\"/>"
+    );
+    assert_eq!(
+        try_html_string! { a(dyn href = UrlAttr(link)); }.unwrap(),
+        "<a href=\"/a?b=1&amp;c=2\"/>"
+    );
+    assert_eq!(
+        try_html_string! { a(dyn href = UrlAttr(&None::<&str>)); }.unwrap(),
+        "<a/>"
+    );
+    assert_eq!(
+        try_html_string! { a(dyn href = TrustedUrl(evil)); }.unwrap(),
+        "<a href=\"javascript:alert(1)\"/>"
+    );
+}